@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+use serde_json::{json, Map, Value};
+use crate::input::read_day_map;
+use crate::{solver, Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(Error::parse(1, format!("invalid phase: {s:?}"))),
+        }
+    }
+}
+
+/// All the timings taken for one phase of one day.
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub samples: Vec<Duration>,
+}
+
+impl Measurement {
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut s = self.samples.clone();
+        s.sort();
+        match s.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 1 => s[n / 2],
+            n => (s[n / 2 - 1] + s[n / 2]) / 2,
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            n => self.samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+/// Runs each part of `day` `iterations` times. The input is parsed afresh
+/// for every run, so the parse phase gets a sample from each of them.
+pub fn bench(day: u8, input: &str, iterations: usize) -> Result<Vec<Measurement>> {
+    let solve = solver(day).ok_or_else(|| Error::no_solution(format!("there is no day {day}")))?;
+    let mut out: Vec<Measurement> = [Phase::Parse, Phase::Part1, Phase::Part2]
+        .into_iter()
+        .map(|phase| Measurement { day, phase, samples: Vec::with_capacity(iterations * 2) })
+        .collect();
+    for _ in 0 .. iterations {
+        for part in 1 ..= 2 {
+            if let Some(a) = solve(input, Some(part))?.first() {
+                out[0].samples.push(a.parse_time);
+                out[part as usize].samples.push(a.solve_time);
+            }
+        }
+    }
+    out.retain(|m| !m.samples.is_empty());
+    Ok(out)
+}
+
+/// Saved median timings in nanoseconds, read from and written to JSON of
+/// the form `{"15": {"parse": 1200, "part1": 53000, "part2": 810000000}}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(u8, Phase), u64>);
+
+impl Baseline {
+    pub fn get(&self, day: u8, phase: Phase) -> Option<Duration> {
+        self.0.get(&(day, phase)).map(|ns| Duration::from_nanos(*ns))
+    }
+
+    /// Records the medians of `measurements`, replacing any earlier entries
+    /// for the same days and phases.
+    pub fn update(&mut self, measurements: &[Measurement]) {
+        for m in measurements {
+            self.0.insert((m.day, m.phase), m.median().as_nanos() as u64);
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut days: BTreeMap<u8, Map<String, Value>> = BTreeMap::new();
+        for ((day, phase), ns) in &self.0 {
+            days.entry(*day).or_default().insert(phase.as_str().into(), json!(ns));
+        }
+        let days: Map<String, Value> = days
+            .into_iter()
+            .map(|(d, phases)| (d.to_string(), Value::Object(phases)))
+            .collect();
+        serde_json::to_string_pretty(&Value::Object(days)).unwrap()
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut out = BTreeMap::new();
+        read_day_map(s, "phases", |d, phase, ns| {
+            let Ok(p) = phase.parse::<Phase>() else {
+                return Err(format!("day {d}: invalid phase: {phase:?}"));
+            };
+            let Some(ns) = ns.as_u64() else {
+                return Err(format!("day {d} {phase}: expected a number of nanoseconds"));
+            };
+            out.insert((d, p), ns);
+            Ok(())
+        })?;
+        Ok(Baseline(out))
+    }
+}
+
+/// The change from `baseline` to `current` as a signed percentage.
+pub fn change(baseline: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_test() {
+        let m = bench(25, "5764801\n17807724\n", 3).unwrap();
+        assert_eq!(m.iter().map(|m| (m.phase, m.samples.len())).collect::<Vec<_>>(),
+            vec![(Phase::Parse, 3), (Phase::Part1, 3)]);
+        assert!(bench(25, "5764801\n", 1).is_err());
+
+        let m = Measurement {
+            day: 15,
+            phase: Phase::Part2,
+            samples: [40, 10, 30, 20].into_iter().map(Duration::from_nanos).collect(),
+        };
+        assert_eq!(m.min(), Duration::from_nanos(10));
+        assert_eq!(m.median(), Duration::from_nanos(25));
+        assert_eq!(m.mean(), Duration::from_nanos(25));
+
+        let mut baseline: Baseline = r#"{"15": {"parse": 100, "part2": 50}}"#.parse().unwrap();
+        baseline.update(&[m]);
+        assert_eq!(baseline.get(15, Phase::Part2), Some(Duration::from_nanos(25)));
+        assert_eq!(baseline.to_json().parse::<Baseline>(), Ok(baseline.clone()));
+        assert_eq!(change(Duration::from_nanos(50), Duration::from_nanos(25)), -50.0);
+        assert_eq!(
+            r#"{"15": {"part3": 1}}"#.parse::<Baseline>(),
+            Err(Error::Parse { line: 1, column: 9, message: "day 15: invalid phase: \"part3\"".into() }),
+        );
+    }
+}
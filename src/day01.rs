@@ -0,0 +1,53 @@
+use std::vec::Vec;
+use itertools::Itertools;
+use crate::input::read_lines;
+use crate::{Error, Result, Solver};
+
+fn part1(input: &[i32]) -> Result<i32> {
+    input.iter()
+        .tuple_combinations()
+        .find(|(a, b)| *a + *b == 2020)
+        .map(|(a, b)| a * b)
+        .ok_or(Error::no_solution("no two entries sum to 2020"))
+}
+
+fn part2(input: &[i32]) -> Result<i32> {
+    input.iter()
+        .tuple_combinations()
+        .find(|(a, b, c)| *a + *b + *c == 2020)
+        .map(|(a, b, c)| a * b * c)
+        .ok_or(Error::no_solution("no three entries sum to 2020"))
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_lines(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day01_test() {
+        let input:Vec<i32> = test_input(include_str!("day01.testinput"));
+        assert_eq!(part1(&input), Ok(514579));
+        assert_eq!(part2(&input), Ok(241861950));
+        assert!(matches!(part1(&[1, 2]), Err(Error::NoSolution(_))));
+    }
+}
@@ -0,0 +1,105 @@
+use std::vec::Vec;
+use std::str::FromStr;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::input::{field, read_lines};
+use crate::{Error, Result, Solver};
+
+#[derive(Debug)]
+pub struct Pass {
+    min: usize,
+    max: usize,
+    c: char,
+    pass: String,
+}
+
+impl Pass {
+    fn is_valid_part1(&self) -> bool {
+        let count = self.pass
+            .chars()
+            .filter(|c| *c == self.c)
+            .count();
+
+        count >= self.min && count <= self.max
+    }
+
+    fn is_valid_part2(&self) -> bool {
+        let chars: Vec<char> = self.pass.chars().collect();
+        let a = chars.get(self.min-1) == Some(&self.c);
+        let b = chars.get(self.max-1) == Some(&self.c);
+
+        a != b
+    }
+}
+
+impl FromStr for Pass {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(\d+)-(\d+) (\w): (\w+)").unwrap();
+        }
+        match RE.captures(s) {
+            None => Err(Error::parse(1, "expected \"<min>-<max> <char>: <password>\"")),
+            Some(caps) => {
+                let m = caps.get(1).unwrap();
+                let min:usize = field(m.as_str(), m.start())?;
+                if min == 0 {
+                    return Err(Error::parse(m.start() + 1, "positions start at 1"));
+                }
+                let m = caps.get(2).unwrap();
+                let max:usize = field(m.as_str(), m.start())?;
+                if max < min {
+                    return Err(Error::parse(m.start() + 1, "max is less than min"));
+                }
+                let c:char = caps.get(3).unwrap().as_str().chars().next().unwrap();
+                let pass:String = caps.get(4).unwrap().as_str().to_string();
+                Ok(Pass {min, max, c, pass})
+            },
+        }
+    }
+}
+
+fn part1(input: &[Pass]) -> usize {
+    input.iter().filter(|p| p.is_valid_part1()).count()
+}
+
+fn part2(input: &[Pass]) -> usize {
+    input.iter().filter(|p| p.is_valid_part2()).count()
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Pass>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_lines(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(Ok(part2(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day02_test() {
+        let input:Vec<Pass> = test_input(include_str!("day02.testinput"));
+        assert_eq!(part1(&input), 2);
+        assert_eq!(part2(&input), 1);
+        assert_eq!(
+            "1-3 a abcde".parse::<Pass>().unwrap_err(),
+            Error::parse(1, "expected \"<min>-<max> <char>: <password>\""),
+        );
+        assert_eq!("3-1 a: abcde".parse::<Pass>().unwrap_err(), Error::parse(3, "max is less than min"));
+    }
+}
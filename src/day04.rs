@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::input::numbered_groups;
+use crate::{Error, Result, Solver};
+
+const EXPECTED_FIELDS: &[&str] = &[
+    "byr", // (Birth Year)
+    "iyr", // (Issue Year)
+    "eyr", // (Expiration Year)
+    "hgt", // (Height)
+    "hcl", // (Hair Color)
+    "ecl", // (Eye Color)
+    "pid", // (Passport ID)
+    //"cid", // (Country ID)
+];
+
+pub struct Passport {
+    data: HashMap<String, String>,
+}
+
+impl Passport {
+    fn is_valid(&self) -> bool {
+        EXPECTED_FIELDS.iter().all(|f| {
+            self.data.contains_key(*f)
+        })
+    }
+    fn is_valid_2(&self) -> bool {
+        if !self.is_valid() { return false; }
+        for (key, val) in &self.data {
+            match key.as_str() {
+                "byr" => {
+                    if let Ok(v) = i32::from_str(val) {
+                        if !(1920..=2002).contains(&v) {
+                            return false;
+                        }
+                    } else {
+                        return false;
+                    }
+                },
+                "iyr" => {
+                    if let Ok(v) = i32::from_str(val) {
+                        if !(2010..=2020).contains(&v) {
+                            return false;
+                        }
+                    } else {
+                        return false;
+                    }
+                },
+                "eyr" => {
+                    if let Ok(v) = i32::from_str(val) {
+                        if !(2020..=2030).contains(&v) {
+                            return false;
+                        }
+                    } else {
+                        return false;
+                    }
+                },
+                "hgt" => {
+                    lazy_static! {
+                        static ref RE: Regex = Regex::new(r"^(\d+)(in|cm)$").unwrap();
+                    }
+                    if let Some(cap) = RE.captures(val) {
+                        let Ok(n) = cap.get(1).unwrap().as_str().parse::<i32>() else {
+                            return false;
+                        };
+                        let u = cap.get(2).unwrap().as_str();
+                        if u == "in" {
+                            if !(59..=76).contains(&n) {
+                                return false;
+                            }
+                        } else if !(150..=193).contains(&n) {
+                            return false;
+                        }
+                    } else {
+                        return false;
+                    }
+                },
+                "hcl" => {
+                    lazy_static! {
+                        static ref RE: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
+                    }
+                    if !RE.is_match(val) {
+                        return false;
+                    }
+                },
+                "ecl" => {
+                    lazy_static! {
+                        static ref RE: Regex = Regex::new(r"^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
+                    }
+                    if !RE.is_match(val) {
+                        return false;
+                    }
+                },
+                "pid" => {
+                    lazy_static! {
+                        static ref RE: Regex = Regex::new(r"^\d{9}$").unwrap();
+                    }
+                    if !RE.is_match(val) {
+                        return false;
+                    }
+                },
+                _ => {},
+            }
+        }
+        true
+    }
+}
+
+impl FromStr for Passport {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"\S+").unwrap();
+            static ref FIELD_RE: Regex = Regex::new(r"^(\w{3}):(\S+)$").unwrap();
+        }
+        let mut map: HashMap<String, String> = HashMap::new();
+        for m in RE.find_iter(s) {
+            let Some(cap) = FIELD_RE.captures(m.as_str()) else {
+                return Err(Error::parse(m.start() + 1, format!("expected \"key:value\", found {:?}", m.as_str())));
+            };
+            let key:String = cap.get(1).unwrap().as_str().to_string();
+            let val:String = cap.get(2).unwrap().as_str().to_string();
+            map.insert(key, val);
+        }
+        if !map.is_empty() {
+            Ok(Passport {data: map})
+        }
+        else {
+            Err(Error::parse(1, "no passport fields"))
+        }
+    }
+}
+
+fn part1(input: &[Passport]) -> usize {
+    input.iter().filter(|p| p.is_valid()).count()
+}
+
+fn part2(input: &[Passport]) -> usize {
+    input.iter().filter(|p| p.is_valid_2()).count()
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        numbered_groups(input)
+            .into_iter()
+            .map(|lineset| {
+                let mut data = HashMap::new();
+                for (n, line) in lineset {
+                    data.extend(line.parse::<Passport>().map_err(|e| e.at_line(n))?.data);
+                }
+                Ok(Passport { data })
+            })
+            .collect()
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(Ok(part2(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::grouped_test_input;
+
+    #[test]
+    fn day04_test() {
+        let input:Vec<Vec<String>> = grouped_test_input(include_str!("day04.testinput"));
+        let input = input.into_iter()
+            .map(|lineset| Passport::from_str(&lineset.join(" ")).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(part1(&input), 2);
+        assert_eq!(part2(&input), 2);
+
+        for i in [
+"eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926",
+"iyr:2019 hcl:#602927 eyr:1967 hgt:170cm ecl:grn pid:012533040 byr:1946",
+"hcl:dab227 iyr:2012 ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277",
+"hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 byr:2007",
+        ] {
+            let p = i.parse::<Passport>().unwrap();
+            assert!(!p.is_valid_2());
+        }
+
+        for i in [
+"pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f",
+"eyr:2029 ecl:blu cid:129 byr:1989 iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm",
+"hcl:#888785 hgt:164cm byr:2001 iyr:2015 cid:88 pid:545766238 ecl:hzl eyr:2022",
+"iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+        ] {
+            let p = i.parse::<Passport>().unwrap();
+            assert!(p.is_valid_2());
+        }
+
+        assert_eq!(
+            Day04::parse("ecl:gry pid:860033327\n\nhcl:#fffffd byr 1937").err(),
+            Some(Error::Parse { line: 3, column: 13, message: "expected \"key:value\", found \"byr\"".into() }),
+        );
+    }
+}
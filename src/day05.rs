@@ -0,0 +1,81 @@
+use std::vec::Vec;
+use std::str::FromStr;
+use crate::input::read_lines;
+use crate::{Error, Result, Solver};
+
+struct SeatID(String);
+
+impl FromStr for SeatID {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((col, c)) = s.char_indices().find(|(_, c)| !"FBLR".contains(*c)) {
+            return Err(Error::parse(col + 1, format!("unexpected character {c:?}")));
+        }
+        if s.len() >= usize::BITS as usize {
+            return Err(Error::parse(usize::BITS as usize, "seat ID is too long"));
+        }
+        Ok(SeatID(s.to_owned()))
+    }
+}
+
+impl From<SeatID> for usize {
+    fn from(s: SeatID) -> Self {
+        usize::from_str_radix(
+            &(s.0).replace('B', "1").replace('F', "0").replace('R', "1").replace('L', "0"),
+            2,
+        ).unwrap()
+    }
+}
+
+fn part1(input: &[usize]) -> Result<usize> {
+    input.iter().copied().max().ok_or(Error::no_solution("no boarding passes"))
+}
+
+fn part2(input: &[usize]) -> Result<usize> {
+    input.windows(2)
+        .find(|a| a[0] + 2 == a[1])
+        .map(|a| a[0] + 1)
+        .ok_or(Error::no_solution("no empty seat between two taken seats"))
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u8 = 5;
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input: Vec<SeatID> = read_lines(input)?;
+        let mut input: Vec<usize> = input.into_iter().map(|s| s.into()).collect();
+        input.sort_unstable();
+        Ok(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day05_test() {
+        let si: usize = "FBFBBFFRLR".parse::<SeatID>().unwrap().into();
+        assert_eq!(si, 357);
+        let si: usize = "BFFFBBFRRR".parse::<SeatID>().unwrap().into();
+        assert_eq!(si, 567);
+        let si: usize = "FFFBBBFRRR".parse::<SeatID>().unwrap().into();
+        assert_eq!(si, 119);
+        let si: usize = "BBFFBBFRLL".parse::<SeatID>().unwrap().into();
+        assert_eq!(si, 820);
+        let p2 = [6,7,8,9,11,12];
+        assert_eq!(part2(&p2), Ok(10));
+        assert_eq!("FBFXBFFRLR".parse::<SeatID>().err(), Some(Error::parse(4, "unexpected character 'X'")));
+    }
+}
@@ -0,0 +1,918 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+use crate::input::{field, read_lines};
+use crate::{Error, Result, Solver};
+
+/// The most registers a program can use, `r0` to `r15`.
+pub const MAX_REGS: usize = 16;
+
+/// How an instruction's operand is written and what it means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperandKind {
+    /// A signed number.
+    Imm,
+    /// A jump offset relative to the instruction, written as a number or as
+    /// a label.
+    Offset,
+    /// A register, `r0` to `r15`. `acc` is `r0`.
+    Reg,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Imm(isize),
+    Reg(usize),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Imm(n) => write!(f, "{n:+}"),
+            Operand::Reg(r) => write!(f, "r{r}"),
+        }
+    }
+}
+
+/// What happens to `pc` after an instruction has executed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    Jump(isize),
+}
+
+/// Where control can go after an instruction, for static analysis. Jumps go
+/// by the instruction's `Offset` operand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Always carries on with the next instruction.
+    Next,
+    /// Always jumps.
+    Jump,
+    /// Either jumps or carries on with the next instruction.
+    Branch,
+}
+
+/// Executes an instruction's operation. Errors become [`Trap::Fault`].
+pub type ExecFn = fn(&mut Cpu, &[Operand]) -> Result<Flow, String>;
+
+/// The definition of an opcode. Instructions point at one of these, so a new
+/// opcode only needs a `static OpSpec` and an entry in the [`OpTable`] used
+/// to parse programs that contain it.
+#[derive(Debug)]
+pub struct OpSpec {
+    pub name: &'static str,
+    pub operands: &'static [OperandKind],
+    pub control: Control,
+    pub exec: ExecFn,
+}
+
+pub static NOP: OpSpec = OpSpec {
+    name: "nop", operands: &[OperandKind::Offset], control: Control::Next, exec: exec_nop,
+};
+pub static JMP: OpSpec = OpSpec {
+    name: "jmp", operands: &[OperandKind::Offset], control: Control::Jump, exec: exec_jmp,
+};
+pub static ACC: OpSpec = OpSpec {
+    name: "acc", operands: &[OperandKind::Imm], control: Control::Next, exec: exec_acc,
+};
+
+fn exec_nop(_: &mut Cpu, _: &[Operand]) -> Result<Flow, String> {
+    Ok(Flow::Next)
+}
+fn exec_jmp(cpu: &mut Cpu, args: &[Operand]) -> Result<Flow, String> {
+    Ok(Flow::Jump(cpu.value(args[0])))
+}
+fn exec_acc(cpu: &mut Cpu, args: &[Operand]) -> Result<Flow, String> {
+    cpu.regs[0] += cpu.value(args[0]);
+    Ok(Flow::Next)
+}
+
+/// The opcodes known to the parser and assembler.
+#[derive(Debug)]
+pub struct OpTable(Vec<&'static OpSpec>);
+
+impl Default for OpTable {
+    /// The handheld's instruction set: `nop`, `jmp` and `acc`.
+    fn default() -> Self {
+        OpTable(vec![&NOP, &JMP, &ACC])
+    }
+}
+
+impl OpTable {
+    /// Adds an opcode, replacing any existing one with the same name.
+    pub fn add(&mut self, spec: &'static OpSpec) {
+        self.0.retain(|s| s.name != spec.name);
+        self.0.push(spec);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&'static OpSpec> {
+        self.0.iter().find(|s| s.name == name).copied()
+    }
+
+    /// Parses one instruction starting at (0-based) byte `offset` of its
+    /// line. Labels used as jump offsets are looked up with `label`, which
+    /// gets the label's name and returns the offset to it; their names are
+    /// returned alongside the instruction.
+    fn parse_inst<F>(&self, s: &str, offset: usize, label: F) -> Result<(Instruction, Vec<Option<String>>)>
+    where F: Fn(&str) -> Option<isize> {
+        let mut tokens = tokens(s, offset);
+        let Some((col, name)) = tokens.next() else {
+            return Err(Error::parse(offset + 1, "expected an instruction"));
+        };
+        let Some(op) = self.get(name) else {
+            return Err(Error::parse(col + 1, format!("invalid instruction: {name}")));
+        };
+        let mut args = Vec::with_capacity(op.operands.len());
+        let mut refs = Vec::with_capacity(op.operands.len());
+        let args_col = col + name.len() + 1;
+        for (i, kind) in op.operands.iter().enumerate() {
+            let Some((col, t)) = tokens.next() else {
+                return Err(Error::parse(args_col.min(offset + s.len()) + 1, format!(
+                    "{name} takes {} operand{}", op.operands.len(), if op.operands.len() == 1 { "" } else { "s" },
+                )));
+            };
+            let mut r = None;
+            args.push(match kind {
+                OperandKind::Reg => Operand::Reg(parse_reg(t).ok_or_else(|| Error::parse(
+                    col + 1, format!("operand {} of {name} must be a register, not {t:?}", i + 1),
+                ))?),
+                OperandKind::Offset if is_label(t) => {
+                    r = Some(t.to_string());
+                    Operand::Imm(label(t).ok_or_else(|| Error::parse(col + 1, format!("undefined label: {t}")))?)
+                },
+                OperandKind::Imm | OperandKind::Offset => Operand::Imm(field::<isize>(t, col)?),
+            });
+            refs.push(r);
+        }
+        if let Some((col, t)) = tokens.next() {
+            return Err(Error::parse(col + 1, format!("unexpected {t:?} after the operands of {name}")));
+        }
+        Ok((Instruction { op, args }, refs))
+    }
+}
+
+/// The whitespace separated tokens of `s`, with their byte offsets within
+/// the line that `s` starts at `offset` of.
+fn tokens(s: &str, offset: usize) -> impl Iterator<Item=(usize, &str)> {
+    s.split_whitespace().map(move |t| (t.as_ptr() as usize - s.as_ptr() as usize + offset, t))
+}
+
+fn parse_reg(s: &str) -> Option<usize> {
+    if s == "acc" {
+        return Some(0);
+    }
+    let n = s.strip_prefix('r')?;
+    if n.starts_with('+') {
+        return None;
+    }
+    n.parse::<usize>().ok().filter(|r| *r < MAX_REGS)
+}
+
+fn is_label(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Clone)]
+pub struct Instruction {
+    pub op: &'static OpSpec,
+    pub args: Vec<Operand>,
+}
+
+impl Instruction {
+    pub fn nop(arg: isize) -> Self {
+        Instruction { op: &NOP, args: vec![Operand::Imm(arg)] }
+    }
+    pub fn jmp(arg: isize) -> Self {
+        Instruction { op: &JMP, args: vec![Operand::Imm(arg)] }
+    }
+    pub fn acc(arg: isize) -> Self {
+        Instruction { op: &ACC, args: vec![Operand::Imm(arg)] }
+    }
+
+    /// The address this instruction jumps to from `addr`, if it has an
+    /// `Offset` operand and the target is inside the program or just after it.
+    fn jump_target(&self, addr: usize, len: usize) -> Option<usize> {
+        self.op.operands.iter().zip(&self.args).find_map(|(kind, arg)| match (kind, arg) {
+            (OperandKind::Offset, Operand::Imm(off)) => (addr as isize).checked_add(*off)
+                .and_then(|t| usize::try_from(t).ok())
+                .filter(|t| *t <= len),
+            _ => None,
+        })
+    }
+
+    /// Where control can go after this instruction at `addr`. Jumps out of
+    /// bounds trap, so they have no successor.
+    fn successors(&self, addr: usize, len: usize) -> Vec<usize> {
+        let target = self.jump_target(addr, len);
+        match self.op.control {
+            Control::Next => vec![addr + 1],
+            Control::Jump => target.into_iter().collect(),
+            Control::Branch => [Some(addr + 1), target].into_iter().flatten().collect(),
+        }
+    }
+
+    /// The instruction with `nop` and `jmp` swapped.
+    pub fn flipped(&self) -> Option<Instruction> {
+        let op: &'static OpSpec = match self.op.name {
+            "nop" => &JMP,
+            "jmp" => &NOP,
+            _ => { return None; },
+        };
+        Some(Instruction { op, args: self.args.clone() })
+    }
+}
+
+impl PartialEq for Instruction {
+    fn eq(&self, other: &Self) -> bool {
+        self.op.name == other.op.name && self.args == other.args
+    }
+}
+impl Eq for Instruction {}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Instruction({self})")
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.op.name)?;
+        for a in &self.args {
+            write!(f, " {a}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OpTable::default().parse_inst(s, 0, |_| None).map(|(inst, _)| inst)
+    }
+}
+
+/// One line of an assembly listing.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Line {
+    pub label: Option<String>,
+    pub inst: Option<Instruction>,
+    /// For each operand of `inst`, the label it was written as, if any.
+    pub refs: Vec<Option<String>>,
+    pub comment: Option<String>,
+}
+
+/// An assembled program that keeps the labels and comments of its source,
+/// so it can be turned back into the same text. Lines look like
+///
+/// ```text
+/// loop: acc +1     ; a comment
+///       jmp loop
+/// ```
+///
+/// with the label, instruction and comment each optional.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Listing {
+    pub lines: Vec<Line>,
+}
+
+impl Listing {
+    pub fn assemble(table: &OpTable, source: &str) -> Result<Self> {
+        let mut lines = Vec::new();
+        let mut labels: HashMap<&str, usize> = HashMap::new();
+        let mut addr = 0;
+        // (line number, code, start of the instruction, listing index, address)
+        let mut insts = Vec::new();
+        for (idx, text) in source.lines().enumerate() {
+            let n = idx + 1;
+            let text = text.trim_end_matches('\r');
+            let (code, comment) = match text.split_once(';') {
+                Some((code, c)) => (code, Some(c.trim().to_string())),
+                None => (text, None),
+            };
+            let mut line = Line { comment, ..Line::default() };
+            let mut start = 0;
+            if let Some((col, t)) = tokens(code, 0).next() {
+                if let Some(name) = t.strip_suffix(':') {
+                    if !is_label(name) {
+                        return Err(Error::parse(col + 1, format!("invalid label: {name:?}")).at_line(n));
+                    }
+                    if labels.insert(name, addr).is_some() {
+                        return Err(Error::parse(col + 1, format!("label {name} is defined twice")).at_line(n));
+                    }
+                    line.label = Some(name.to_string());
+                    start = col + t.len();
+                }
+            }
+            if !code[start..].trim().is_empty() {
+                insts.push((n, code, start, lines.len(), addr));
+                addr += 1;
+            }
+            lines.push(line);
+        }
+        for (n, code, start, line, addr) in insts {
+            let (inst, refs) = table
+                .parse_inst(&code[start..], start, |l| labels.get(l).map(|a| *a as isize - addr as isize))
+                .map_err(|e| e.at_line(n))?;
+            lines[line].inst = Some(inst);
+            lines[line].refs = refs;
+        }
+        Ok(Listing { lines })
+    }
+
+    /// A listing of `code` with no comments, where every jump target gets a
+    /// label `L<address>`.
+    pub fn disassemble(code: &[Instruction]) -> Self {
+        let mut targets = vec![false; code.len() + 1];
+        for (addr, inst) in code.iter().enumerate() {
+            if let Some(t) = inst.jump_target(addr, code.len()) {
+                targets[t] = true;
+            }
+        }
+        let label = |addr: usize| targets[addr].then(|| format!("L{addr}"));
+        let mut lines: Vec<Line> = code.iter().enumerate().map(|(addr, inst)| Line {
+            label: label(addr),
+            inst: Some(inst.clone()),
+            refs: inst.op.operands.iter()
+                .map(|kind| match kind {
+                    OperandKind::Offset => inst.jump_target(addr, code.len()).map(|t| format!("L{t}")),
+                    _ => None,
+                })
+                .collect(),
+            comment: None,
+        }).collect();
+        if targets[code.len()] {
+            lines.push(Line { label: label(code.len()), ..Line::default() });
+        }
+        Listing { lines }
+    }
+
+    pub fn code(&self) -> Vec<Instruction> {
+        self.lines.iter().filter_map(|l| l.inst.clone()).collect()
+    }
+}
+
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            let mut parts = Vec::new();
+            if let Some(l) = &line.label {
+                parts.push(format!("{l}:"));
+            }
+            if let Some(inst) = &line.inst {
+                let mut s = inst.op.name.to_string();
+                for (i, a) in inst.args.iter().enumerate() {
+                    match line.refs.get(i) {
+                        Some(Some(r)) => { s += &format!(" {r}"); },
+                        _ => { s += &format!(" {a}"); },
+                    }
+                }
+                parts.push(s);
+            }
+            if let Some(c) = &line.comment {
+                parts.push(format!("; {c}").trim_end().to_string());
+            }
+            writeln!(f, "{}", parts.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The state an [`ExecFn`] works on.
+#[derive(Clone, Debug, Default)]
+pub struct Cpu {
+    pub pc: usize,
+    pub regs: Vec<isize>,
+    pub input: VecDeque<isize>,
+    pub output: Vec<isize>,
+}
+
+impl Cpu {
+    pub fn value(&self, arg: Operand) -> isize {
+        match arg {
+            Operand::Imm(n) => n,
+            Operand::Reg(r) => self.regs[r],
+        }
+    }
+
+    /// Sets the register `arg`; the assembler only allows registers where an
+    /// opcode's operand kind is `Reg`.
+    pub fn set(&mut self, arg: Operand, val: isize) {
+        if let Operand::Reg(r) = arg {
+            self.regs[r] = val;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stop before executing the instruction at this address.
+    Pc(usize),
+    /// Stop before executing any instruction with this opcode.
+    Op(&'static str),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Registers {
+    pub pc: usize,
+    pub regs: Vec<isize>,
+}
+
+impl Registers {
+    pub fn acc(&self) -> isize {
+        self.regs[0]
+    }
+}
+
+/// One executed instruction, with the registers as they were before it ran.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub regs: Registers,
+    pub inst: Instruction,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:5}: {:10} acc={}", self.regs.pc, self.inst.to_string(), self.regs.acc())?;
+        for (r, v) in self.regs.regs.iter().enumerate().skip(1) {
+            write!(f, " r{r}={v}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A fault that stops the VM.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Trap {
+    /// A jump to before the first instruction or past the one just after
+    /// the last (jumping to that one is how a program terminates).
+    JumpOutOfBounds { pc: usize, target: isize, len: usize },
+    /// An error returned by an opcode's [`ExecFn`].
+    Fault { pc: usize, message: String },
+    /// `step` was called on a VM that has already halted.
+    Halted,
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Trap::JumpOutOfBounds { pc, target, len } =>
+                write!(f, "jump at {pc} to {target} is outside the program (0..={len})"),
+            Trap::Fault { pc, message } => write!(f, "fault at {pc}: {message}"),
+            Trap::Halted => write!(f, "the program has already halted"),
+        }
+    }
+}
+
+impl From<Trap> for Error {
+    fn from(t: Trap) -> Self {
+        Error::no_solution(t.to_string())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunResult {
+    Ok,
+    Halt(isize),
+    /// The VM was about to get back into a state it had already been in, so
+    /// it would run forever. For programs that don't branch on data (like
+    /// the handheld's) the state is just `pc`; otherwise it's `pc` and the
+    /// registers.
+    Loop(isize),
+    Break(Registers),
+}
+
+pub struct VM<'a> {
+    cpu: Cpu,
+    program: &'a [Instruction],
+    inst_counter: Vec<usize>,
+    seen: HashSet<(usize, Vec<isize>)>,
+    pc_only: bool,
+    breakpoints: Vec<Breakpoint>,
+    trace: Option<Vec<TraceEntry>>,
+    resuming: bool,
+}
+impl<'a> VM<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        let len = program.len();
+        let nregs = program.iter()
+            .flat_map(|i| i.args.iter())
+            .filter_map(|a| match a { Operand::Reg(r) => Some(r + 1), _ => None })
+            .max()
+            .unwrap_or(1);
+        VM {
+            cpu: Cpu { regs: vec![0; nregs], ..Cpu::default() },
+            program,
+            inst_counter: vec![0; len],
+            seen: HashSet::new(),
+            pc_only: program.iter().all(|i| i.op.control != Control::Branch),
+            breakpoints: Vec::new(),
+            trace: None,
+            resuming: false,
+        }
+    }
+
+    pub fn registers(&self) -> Registers {
+        Registers { pc: self.cpu.pc, regs: self.cpu.regs.clone() }
+    }
+
+    /// Puts the registers back to an earlier snapshot, so that `run` replays
+    /// from there. Loop detection starts afresh from the snapshot; the
+    /// execution counts are left alone.
+    pub fn restore(&mut self, regs: Registers) {
+        self.cpu.pc = regs.pc;
+        self.cpu.regs = regs.regs;
+        self.seen.clear();
+        self.resuming = false;
+    }
+
+    /// Queues a value for instructions that read input.
+    pub fn push_input(&mut self, val: isize) {
+        self.cpu.input.push_back(val);
+    }
+
+    pub fn output(&self) -> &[isize] {
+        &self.cpu.output
+    }
+
+    /// How many times the instruction at each address has been executed.
+    pub fn counts(&self) -> &[usize] {
+        &self.inst_counter
+    }
+
+    pub fn add_breakpoint(&mut self, bp: Breakpoint) {
+        if !self.breakpoints.contains(&bp) {
+            self.breakpoints.push(bp);
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, bp: Breakpoint) {
+        self.breakpoints.retain(|b| *b != bp);
+    }
+
+    /// Starts (or stops) recording every executed instruction.
+    pub fn set_trace(&mut self, on: bool) {
+        self.trace = if on { Some(self.trace.take().unwrap_or_default()) } else { None };
+    }
+
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.cpu.pc >= self.program.len()
+    }
+
+    /// Executes the instruction at `pc`, ignoring breakpoints.
+    pub fn step(&mut self) -> Result<RunResult, Trap> {
+        let pc = self.cpu.pc;
+        let Some(inst) = self.program.get(pc) else {
+            return Err(Trap::Halted);
+        };
+        self.resuming = false;
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry { regs: Registers { pc, regs: self.cpu.regs.clone() }, inst: inst.clone() });
+        }
+        self.inst_counter[pc] += 1;
+        let state = self.state();
+        self.seen.insert(state);
+
+        match (inst.op.exec)(&mut self.cpu, &inst.args) {
+            Ok(Flow::Next) => { self.cpu.pc = pc + 1; },
+            Ok(Flow::Jump(arg)) => {
+                let len = self.program.len();
+                match (pc as isize).checked_add(arg) {
+                    Some(target) if target >= 0 && target as usize <= len => { self.cpu.pc = target as usize; },
+                    // an overflowing target is reported as the nearest isize
+                    target => {
+                        let target = target.unwrap_or((pc as isize).saturating_add(arg));
+                        return Err(Trap::JumpOutOfBounds { pc, target, len });
+                    },
+                }
+            },
+            Err(message) => { return Err(Trap::Fault { pc, message }); },
+        }
+        if self.is_halted() {
+            return Ok(RunResult::Halt(self.cpu.regs[0]));
+        }
+        Ok(RunResult::Ok)
+    }
+
+    fn state(&self) -> (usize, Vec<isize>) {
+        (self.cpu.pc, if self.pc_only { Vec::new() } else { self.cpu.regs.clone() })
+    }
+
+    fn at_breakpoint(&self) -> bool {
+        let op = self.program[self.cpu.pc].op.name;
+        self.breakpoints.iter().any(|bp| match bp {
+            Breakpoint::Pc(pc) => *pc == self.cpu.pc,
+            Breakpoint::Op(o) => *o == op,
+        })
+    }
+
+    /// Runs until the program halts, is about to repeat itself (see
+    /// [`RunResult::Loop`]), or reaches a breakpoint. Calling `run` again after a
+    /// `Break` carries on from the breakpoint.
+    pub fn run(&mut self) -> Result<RunResult, Trap> {
+        if self.is_halted() {
+            return Ok(RunResult::Halt(self.cpu.regs[0]));
+        }
+        loop {
+            if self.seen.contains(&self.state()) {
+                return Ok(RunResult::Loop(self.cpu.regs[0]));
+            }
+            if !self.resuming && self.at_breakpoint() {
+                self.resuming = true;
+                return Ok(RunResult::Break(self.registers()));
+            }
+            if let r @ RunResult::Halt(_) = self.step()? {
+                return Ok(r);
+            }
+        }
+    }
+}
+
+pub fn run(program: &[Instruction]) -> Result<RunResult, Trap> {
+    VM::new(program).run()
+}
+
+fn part1(input: &[Instruction]) -> Result<isize> {
+    match run(input)? {
+        RunResult::Loop(a) => Ok(a),
+        _ => Err(Error::no_solution("the program terminates without looping")),
+    }
+}
+
+/// The control-flow graph of a program. Node `n` is the instruction at
+/// address `n`, and node `len` is the end of the program, which is reached
+/// by terminating normally.
+pub struct Cfg {
+    succ: Vec<Vec<usize>>,
+    pred: Vec<Vec<usize>>,
+}
+
+impl Cfg {
+    pub fn new(code: &[Instruction]) -> Self {
+        let mut succ: Vec<Vec<usize>> = code.iter()
+            .enumerate()
+            .map(|(addr, inst)| inst.successors(addr, code.len()))
+            .collect();
+        succ.push(Vec::new());
+        let mut pred = vec![Vec::new(); succ.len()];
+        for (n, s) in succ.iter().enumerate() {
+            for t in s {
+                pred[*t].push(n);
+            }
+        }
+        Cfg { succ, pred }
+    }
+
+    pub fn end(&self) -> usize {
+        self.succ.len() - 1
+    }
+
+    pub fn successors(&self, n: usize) -> &[usize] {
+        &self.succ[n]
+    }
+
+    fn search(edges: &[Vec<usize>], start: usize) -> Vec<bool> {
+        let mut seen = vec![false; edges.len()];
+        let mut stack = vec![start];
+        seen[start] = true;
+        while let Some(n) = stack.pop() {
+            for m in &edges[n] {
+                if !seen[*m] {
+                    seen[*m] = true;
+                    stack.push(*m);
+                }
+            }
+        }
+        seen
+    }
+
+    /// The nodes that can be reached from the first instruction.
+    pub fn reachable(&self) -> Vec<bool> {
+        Self::search(&self.succ, 0)
+    }
+
+    /// The nodes from which the end of the program can be reached.
+    pub fn reaches_end(&self) -> Vec<bool> {
+        Self::search(&self.pred, self.end())
+    }
+
+    /// The graph in Graphviz DOT format. Instructions that can't be reached
+    /// from the start are dashed and those that can reach the end are green.
+    pub fn to_dot(&self, code: &[Instruction]) -> String {
+        let reachable = self.reachable();
+        let reaches_end = self.reaches_end();
+        let mut out = String::from("digraph program {\n    node [shape=box, fontname=monospace];\n");
+        for (n, inst) in code.iter().enumerate() {
+            let mut attrs = vec![format!("label=\"{n}: {inst}\"")];
+            if !reachable[n] {
+                attrs.push("style=dashed".into());
+            }
+            if reaches_end[n] {
+                attrs.push("color=green".into());
+            }
+            out += &format!("    n{n} [{}];\n", attrs.join(", "));
+        }
+        out += &format!("    n{} [label=\"end\", shape=doublecircle];\n", self.end());
+        for (n, succ) in self.succ.iter().enumerate() {
+            for m in succ {
+                out += &format!("    n{n} -> n{m};\n");
+            }
+        }
+        out += "}\n";
+        out
+    }
+}
+
+/// The single `nop`/`jmp` swaps, as (address, replacement), that could make
+/// the program terminate: the swapped instruction must be reachable from the
+/// start and its new successor must reach the end. Found in linear time.
+pub fn patch_candidates(code: &[Instruction]) -> Vec<(usize, Instruction)> {
+    let cfg = Cfg::new(code);
+    let reachable = cfg.reachable();
+    let reaches_end = cfg.reaches_end();
+    (0 .. code.len())
+        .filter(|i| reachable[*i])
+        .filter_map(|i| code[i].flipped().map(|inst| (i, inst)))
+        .filter(|(i, inst)| inst.successors(*i, code.len()).iter().any(|s| reaches_end[*s]))
+        .collect()
+}
+
+fn part2(input: &[Instruction]) -> Result<isize> {
+    let Some((idx, inst)) = patch_candidates(input).into_iter().next() else {
+        return Err(Error::no_solution("no single nop/jmp swap makes the program terminate"));
+    };
+    let mut program: Vec<Instruction> = input.to_owned();
+    program[idx] = inst;
+    match run(&program)? {
+        RunResult::Halt(a) => Ok(a),
+        _ => Err(Error::no_solution(format!("swapping the instruction at {idx} doesn't make the program terminate"))),
+    }
+}
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    const DAY: u8 = 8;
+    type Input = Vec<Instruction>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_lines(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    static OUT: OpSpec = OpSpec {
+        name: "out", operands: &[OperandKind::Reg], control: Control::Next, exec: exec_out,
+    };
+    static JNZ: OpSpec = OpSpec {
+        name: "jnz", operands: &[OperandKind::Reg, OperandKind::Offset], control: Control::Branch, exec: exec_jnz,
+    };
+    static ADD: OpSpec = OpSpec {
+        name: "add", operands: &[OperandKind::Reg, OperandKind::Imm], control: Control::Next, exec: exec_add,
+    };
+
+    fn exec_out(cpu: &mut Cpu, args: &[Operand]) -> Result<Flow, String> {
+        cpu.output.push(cpu.value(args[0]));
+        Ok(Flow::Next)
+    }
+    fn exec_jnz(cpu: &mut Cpu, args: &[Operand]) -> Result<Flow, String> {
+        Ok(if cpu.value(args[0]) != 0 { Flow::Jump(cpu.value(args[1])) } else { Flow::Next })
+    }
+    fn exec_add(cpu: &mut Cpu, args: &[Operand]) -> Result<Flow, String> {
+        let v = cpu.value(args[0]).checked_add(cpu.value(args[1])).ok_or("overflow")?;
+        cpu.set(args[0], v);
+        Ok(Flow::Next)
+    }
+
+    #[test]
+    fn day08_test() {
+        let input:Vec<Instruction> = read_lines(include_str!("day08.testinput")).unwrap();
+        assert_eq!(part1(&input), Ok(5));
+        assert_eq!(part2(&input), Ok(8));
+        assert_eq!("jmp".parse::<Instruction>().err(), Some(Error::parse(4, "jmp takes 1 operand")));
+        assert_eq!("jmp 4 5".parse::<Instruction>().err(), Some(Error::parse(7, "unexpected \"5\" after the operands of jmp")));
+        assert_eq!("mul +4".parse::<Instruction>().err(), Some(Error::parse(1, "invalid instruction: mul")));
+
+        let mut vm = VM::new(&input);
+        vm.set_trace(true);
+        vm.add_breakpoint(Breakpoint::Op("jmp"));
+        assert_eq!(vm.run(), Ok(RunResult::Break(Registers { pc: 2, regs: vec![1] })));
+        let snapshot = vm.registers();
+        assert_eq!(vm.run(), Ok(RunResult::Break(Registers { pc: 7, regs: vec![2] })));
+        vm.remove_breakpoint(Breakpoint::Op("jmp"));
+        vm.add_breakpoint(Breakpoint::Pc(4));
+        assert_eq!(vm.run(), Ok(RunResult::Break(Registers { pc: 4, regs: vec![5] })));
+        assert_eq!(vm.step(), Ok(RunResult::Ok));
+        assert_eq!(vm.registers(), Registers { pc: 1, regs: vec![5] });
+        assert_eq!(vm.run(), Ok(RunResult::Loop(5)));
+        assert_eq!(vm.trace().len(), 7);
+        assert_eq!(vm.trace()[6].to_string(), "    4: jmp -3     acc=5");
+        vm.restore(snapshot);
+        assert_eq!(vm.registers(), Registers { pc: 2, regs: vec![1] });
+        assert_eq!(vm.run(), Ok(RunResult::Break(Registers { pc: 4, regs: vec![5] })));
+        assert_eq!(vm.run(), Ok(RunResult::Loop(6)));
+        assert_eq!(vm.counts()[4], 2);
+
+        let program = vec![Instruction::acc(3), Instruction::jmp(-2)];
+        assert_eq!(run(&program), Err(Trap::JumpOutOfBounds { pc: 1, target: -1, len: 2 }));
+        assert_eq!(
+            part1(&program),
+            Err(Error::no_solution("jump at 1 to -1 is outside the program (0..=2)")),
+        );
+        let program: Vec<Instruction> = read_lines("acc +1\njmp +9223372036854775807\n").unwrap();
+        assert_eq!(run(&program), Err(Trap::JumpOutOfBounds { pc: 1, target: isize::MAX, len: 2 }));
+        assert!(matches!(solver(8).unwrap()("acc +1\njmp +9223372036854775807\n", None), Err(Error::NoSolution(_))));
+        assert_eq!(Cfg::new(&program).successors(1), &[] as &[usize]);
+        assert_eq!(patch_candidates(&program), vec![(1, Instruction::nop(isize::MAX))]);
+        assert_eq!(part2(&program), Ok(1));
+        assert_eq!(Listing::disassemble(&program).to_string(), "acc +1\njmp +9223372036854775807\n");
+        let program = vec![Instruction::jmp(2), Instruction::acc(1)];
+        let mut vm = VM::new(&program);
+        assert_eq!(vm.run(), Ok(RunResult::Halt(0)));
+        assert_eq!(vm.step(), Err(Trap::Halted));
+
+        let mut table = OpTable::default();
+        table.add(&OUT);
+        table.add(&JNZ);
+        table.add(&ADD);
+        let source = "\
+; count down from 3
+        add r1 +3
+loop: out r1 ; print it
+add r1 -1
+        jnz r1 loop
+acc +1
+";
+        let listing = Listing::assemble(&table, source).unwrap();
+        assert_eq!(listing.to_string(), "\
+; count down from 3
+add r1 +3
+loop: out r1 ; print it
+add r1 -1
+jnz r1 loop
+acc +1
+");
+        assert_eq!(Listing::assemble(&table, &listing.to_string()), Ok(listing.clone()));
+        let code = listing.code();
+        assert_eq!(code[3].args, vec![Operand::Reg(1), Operand::Imm(-2)]);
+        let mut vm = VM::new(&code);
+        assert_eq!(vm.run(), Ok(RunResult::Halt(1)));
+        assert_eq!(vm.output(), &[3, 2, 1]);
+        assert_eq!(vm.registers().regs, vec![1, 0]);
+        let spin = Listing::assemble(&table, "add r1 +1\nl: jnz r1 l\n").unwrap().code();
+        assert_eq!(VM::new(&spin).run(), Ok(RunResult::Loop(0)));
+
+        assert_eq!(
+            Listing::assemble(&table, "a: nop +0\njnz r1 b\n"),
+            Err(Error::Parse { line: 2, column: 8, message: "undefined label: b".into() }),
+        );
+        assert_eq!(
+            Listing::assemble(&table, "out +1\n"),
+            Err(Error::Parse { line: 1, column: 5, message: "operand 1 of out must be a register, not \"+1\"".into() }),
+        );
+        let cfg = Cfg::new(&code);
+        assert_eq!(cfg.successors(3), &[4, 1]);
+        assert_eq!(cfg.reaches_end(), vec![true; 6]);
+
+        let cfg = Cfg::new(&input);
+        assert_eq!(cfg.reachable(), vec![true, true, true, true, true, false, true, true, false, false]);
+        assert_eq!(cfg.reaches_end(), vec![false, false, false, false, false, false, false, false, true, true]);
+        assert_eq!(patch_candidates(&input), vec![(7, Instruction::nop(-4))]);
+        let dot = cfg.to_dot(&input);
+        assert!(dot.contains("    n5 [label=\"5: acc -99\", style=dashed];\n"));
+        assert!(dot.contains("    n8 [label=\"8: acc +6\", style=dashed, color=green];\n"));
+        assert!(dot.contains("    n7 -> n3;\n"));
+
+        assert_eq!(Listing::disassemble(&input).to_string(), "\
+L0: nop L0
+L1: acc +1
+jmp L6
+L3: acc +3
+jmp L1
+acc -99
+L6: acc +1
+jmp L3
+acc +6
+");
+    }
+}
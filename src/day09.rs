@@ -0,0 +1,85 @@
+use itertools::Itertools;
+use crate::input::read_lines;
+use crate::{Error, Result, Solver};
+
+fn first_invalid(input: &[i64], preamble_len: usize) -> Result<i64> {
+    input
+        .windows(preamble_len + 1)
+        .flat_map(|w| {
+            let mut sums = w.iter()
+                .take(preamble_len)
+                .tuple_combinations()
+                .filter(|(a,b)| a != b)
+                .map(|(a,b)| a + b);
+            let m:i64 = w[preamble_len];
+            if !sums.any(|s| s == m) {
+                Some(m)
+            } else {
+                None
+            }
+        })
+        .next()
+        .ok_or(Error::no_solution("every number is the sum of two of the preceding ones"))
+}
+
+fn part1(input: &[i64], preamble_len: usize) -> Result<i64> {
+    first_invalid(input, preamble_len)
+}
+
+fn part2(input: &[i64], preamble_len: usize) -> Result<i64> {
+    let target = first_invalid(input, preamble_len)?;
+
+    for start in 0..input.len() {
+        let mut sum = 0i64;
+        let mut min = input[start];
+        let mut max = input[start];
+        for (len, inp) in input.iter().skip(start).enumerate() {
+            sum += *inp;
+            min = min.min(*inp);
+            max = max.max(*inp);
+            if sum > target { break; }
+            // the range has to be at least two numbers, or the target
+            // itself would always match
+            if sum == target && len >= 1 {
+                return Ok(min + max);
+            }
+        }
+    }
+    Err(Error::no_solution(format!("no contiguous range sums to {target}")))
+}
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_lines(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input, 25)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input, 25))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_advent_lib::read::test_input;
+
+    #[test]
+    fn day09_test() {
+        let input = test_input::<i64>(include_str!("day09.testinput"));
+        assert_eq!(part1(&input, 5), Ok(127));
+        assert_eq!(part2(&input, 5), Ok(62));
+        assert!(matches!(part1(&input, 30), Err(Error::NoSolution(_))));
+        let input: Vec<i64> = (1..=25).chain([1000]).collect();
+        assert_eq!(part1(&input, 25), Ok(1000));
+        assert!(matches!(part2(&input, 25), Err(Error::NoSolution(_))));
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+use crate::input::read_lines;
+use crate::{Error, Result, Solver};
+
+/// The sorted joltage chain from the outlet to the device, checking that
+/// no two neighboring adapters are more than 3 jolts apart.
+fn chain(input: &[i64]) -> Result<Vec<i64>> {
+    let mut list = input.to_owned();
+    list.push(0);
+    list.sort_unstable();
+    list.push(list[list.len() - 1] + 3);
+    if let Some(w) = list.windows(2).find(|w| w[1] - w[0] > 3) {
+        return Err(Error::no_solution(format!("no adapter bridges {} to {} jolts", w[0], w[1])));
+    }
+    Ok(list)
+}
+
+fn part1(input: &[i64]) -> Result<i64> {
+    let list = chain(input)?;
+    let mut hist: HashMap<i64,i64> = HashMap::new();
+
+    list.windows(2)
+        .map(|w| w[1] - w[0])
+        .for_each(|v| {
+            hist.entry(v).and_modify(|c| {*c += 1;}).or_insert(1);
+        });
+
+    Ok(hist.get(&1).unwrap_or(&0) * hist.get(&3).unwrap_or(&0))
+}
+
+fn rsearch(slice: &[i64]) -> Result<usize> {
+    let mut sum = 0;
+    if slice.is_empty() { return Ok(0); }
+    if slice.len() == 1 { return Ok(1); }
+    if slice[1] - slice[0] >= 3 {
+        return Err(Error::no_solution(format!("unexpected gap from {} to {} jolts", slice[0], slice[1])));
+    }
+    sum += rsearch(&slice[1..])?;
+    if slice.len() == 2 { return Ok(sum); }
+    if slice[2] - slice[0] > 3 { return Ok(sum); }
+    sum += rsearch(&slice[2..])?;
+    if slice.len() == 3 { return Ok(sum); }
+    if slice[3] - slice[0] > 3 { return Ok(sum); }
+    sum += rsearch(&slice[3..])?;
+    Ok(sum)
+}
+
+fn part2(input: &[i64]) -> Result<usize> {
+    let list = chain(input)?;
+    let mut sublist: Vec<i64> = Vec::new();
+    let mut result = 1usize;
+    for (idx, w) in list.windows(2).enumerate() {
+        sublist.push(w[0]);
+        if w[1] - w[0] >= 3 {
+            let start = idx - (sublist.len() - 1);
+            let slice = &list[start..=idx];
+            result *= rsearch(slice)?;
+            sublist.clear();
+        }
+    }
+    Ok(result)
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_lines(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day10_test() {
+        let input = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        assert_eq!(part1(&input), Ok(35));
+        assert_eq!(part2(&input), Ok(8));
+
+        let input = vec![28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45, 19, 38, 39, 11, 1, 32, 25, 35, 8, 17, 7, 9, 4, 2, 34, 10, 3];
+        assert_eq!(part1(&input), Ok(220));
+        assert_eq!(part2(&input), Ok(19208));
+
+        let input = vec![1, 2, 7];
+        assert_eq!(part1(&input), Err(Error::no_solution("no adapter bridges 2 to 7 jolts")));
+    }
+}
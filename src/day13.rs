@@ -0,0 +1,118 @@
+use std::vec::Vec;
+use crate::input::{eof_line, field, numbered_lines};
+use crate::{Error, Result, Solver};
+
+pub struct Notes {
+    earliest: usize,
+    buses: Vec<Option<usize>>, // None for 'x'
+}
+
+fn part1(input: &Notes) -> Result<usize> {
+    let ts = input.earliest;
+    let buses = input.buses.iter().flatten();
+    let mut times: Vec<(usize, usize)> = buses
+        .map(|b| ((b - ts % b) % b, *b))
+        .collect();
+    times.sort();
+    times.first()
+        .map(|(wait, bus)| wait * bus)
+        .ok_or(Error::no_solution("no buses in service"))
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn part2(input: &Notes) -> Result<usize> {
+    let buses = input.buses
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, b)| b.map(|b| (idx, b)));
+    let mut period = 1usize;
+    let mut time = 0usize;
+    let mut any = false;
+    for (idx, bus) in buses {
+        any = true;
+        // stepping by `period` only reaches every residue mod `bus` when
+        // they're coprime; if it hasn't found one after `bus` steps it
+        // never will
+        let mut steps = 0;
+        while !(time + idx).is_multiple_of(bus) {
+            if steps == bus {
+                return Err(Error::no_solution(format!(
+                    "bus {bus} can't be at offset {idx} along with the buses before it",
+                )));
+            }
+            time = time.checked_add(period).ok_or(Error::no_solution("the timestamp is too large"))?;
+            steps += 1;
+        }
+        period = (period / gcd(period, bus))
+            .checked_mul(bus)
+            .ok_or(Error::no_solution("the timestamp is too large"))?;
+    }
+    if !any {
+        return Err(Error::no_solution("no buses in service"));
+    }
+    // time 0 works for everything only if each bus's offset is a multiple
+    // of its id; the puzzle wants a time after that
+    Ok(if time == 0 { period } else { time })
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+    type Input = Notes;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = numbered_lines(input);
+        let missing = || Error::parse(1, "expected a timestamp line and a bus line").at_line(eof_line(input));
+        let (n, line) = lines.next().ok_or_else(missing)?;
+        let earliest = field::<usize>(line, 0).map_err(|e| e.at_line(n))?;
+        let (n, line) = lines.next().ok_or_else(missing)?;
+        let mut buses = Vec::new();
+        let mut offset = 0;
+        for s in line.split(',') {
+            buses.push(match s {
+                "x" => None,
+                _ => match field::<usize>(s, offset).map_err(|e| e.at_line(n))? {
+                    0 => { return Err(Error::parse(offset + 1, "bus IDs must be positive").at_line(n)); },
+                    b => Some(b),
+                },
+            });
+            offset += s.len() + 1;
+        }
+        Ok(Notes { earliest, buses })
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day13_test() {
+        let input = Day13::parse("939\n7,13,x,x,59,x,31,19\n").unwrap();
+
+        assert_eq!(part1(&input), Ok(295));
+        assert_eq!(part2(&input), Ok(1068781));
+        assert_eq!(part2(&Day13::parse("0\nx,7\n").unwrap()), Ok(6));
+        assert_eq!(part2(&Day13::parse("0\n4,x,6\n").unwrap()), Ok(4));
+        assert_eq!(
+            part2(&Day13::parse("0\n4,6\n").unwrap()),
+            Err(Error::no_solution("bus 6 can't be at offset 1 along with the buses before it")),
+        );
+        assert_eq!(
+            Day13::parse("939\n7,13,x,y").err(),
+            Some(Error::Parse { line: 2, column: 8, message: "\"y\": invalid digit found in string".into() }),
+        );
+    }
+}
@@ -0,0 +1,467 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::str::FromStr;
+use std::vec::Vec;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use crate::input::{field, numbered_lines};
+use crate::{Error, Result, Solver};
+
+/// The word width used by writes that come before the first mask.
+pub const DEFAULT_WIDTH: u32 = 36;
+
+/// A bitmask whose width is the length of the mask string, up to 128 bits.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Mask {
+    or: u128,
+    and: u128,
+    float: u128,
+    width: u32,
+}
+impl Mask {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Whether `val` fits in the mask's width.
+    pub fn fits(&self, val: u128) -> bool {
+        val.checked_shr(self.width).unwrap_or(0) == 0
+    }
+}
+impl Default for Mask {
+    /// The mask in effect before the first one is set, which changes nothing.
+    fn default() -> Self {
+        Mask { or: 0, and: !0, float: 0, width: DEFAULT_WIDTH }
+    }
+}
+impl FromStr for Mask {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((col, c)) = s.char_indices().find(|(_, c)| !"01X".contains(*c)) {
+            return Err(Error::parse(col + 1, format!("unexpected mask character {c:?}")));
+        }
+        if s.is_empty() || s.len() > 128 {
+            return Err(Error::parse(1, format!("mask must be 1 to 128 bits wide, not {}", s.len())));
+        }
+        let bits = |one: char| u128::from_str_radix(
+            &s.chars().map(|c| if c == one { '1' } else { '0' }).collect::<String>(), 2,
+        );
+        Ok(Mask {
+            or: bits('1')?,
+            and: !bits('0')?,
+            float: bits('X')?,
+            width: s.len() as u32,
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Instruction {
+    SetMask(Mask),
+    SetMem(u128,u128), // (location, value)
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref MASK_RE: Regex = Regex::new(r"^mask = (\S*)$").unwrap();
+            static ref MEM_RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+        }
+        if let Some(caps) = MASK_RE.captures(s) {
+            let m = caps.get(1).unwrap();
+            Ok(Instruction::SetMask(
+                m.as_str().parse::<Mask>().map_err(|e| e.at_offset(m.start()))?
+            ))
+        }
+        else if let Some(caps) = MEM_RE.captures(s) {
+            let (loc, val) = (caps.get(1).unwrap(), caps.get(2).unwrap());
+            Ok(Instruction::SetMem(
+                field(loc.as_str(), loc.start())?,
+                field(val.as_str(), val.start())?,
+            ))
+        }
+        else {
+            Err(Error::parse(1, "expected \"mask = <mask>\" or \"mem[<addr>] = <value>\""))
+        }
+    }
+}
+
+/// The addresses that match `fixed` on every bit that isn't in `float`.
+/// Bits in `float` are always clear in `fixed`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct AddrSet {
+    fixed: u128,
+    float: u128,
+}
+
+impl AddrSet {
+    /// The number of addresses in the set, or `None` if all 128 bits float.
+    fn len(&self) -> Option<u128> {
+        1u128.checked_shl(self.float.count_ones())
+    }
+
+    fn intersects(&self, other: &AddrSet) -> bool {
+        (self.fixed ^ other.fixed) & !self.float & !other.float == 0
+    }
+
+    /// `self` minus `other`, as disjoint sets. Each bit that floats here but
+    /// is fixed in `other` splits off the addresses that disagree with
+    /// `other` on that bit; what's left after the last one is inside `other`.
+    fn subtract(&self, other: &AddrSet) -> Vec<AddrSet> {
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+        let mut out = Vec::new();
+        let mut rest = *self;
+        let mut bits = self.float & !other.float;
+        while bits != 0 {
+            let b = bits & bits.wrapping_neg();
+            bits &= !b;
+            rest.float &= !b;
+            out.push(AddrSet { fixed: rest.fixed | (!other.fixed & b), float: rest.float });
+            rest.fixed |= other.fixed & b;
+        }
+        out
+    }
+}
+
+/// Memory for the v2 decoder. Each write covers every address its mask's
+/// floating bits can produce, so rather than storing addresses it keeps the
+/// writes as disjoint address sets, cutting each new write out of the older
+/// ones. The number of sets depends on how the writes overlap, not on how
+/// many floating bits they have.
+#[derive(Clone, Debug, Default)]
+pub struct FloatingMemory {
+    writes: Vec<(AddrSet, u128)>,
+}
+
+impl FloatingMemory {
+    pub fn write(&mut self, mask: &Mask, addr: u128, val: u128) {
+        let set = AddrSet { fixed: (addr | mask.or) & !mask.float, float: mask.float };
+        self.writes = self.writes
+            .iter()
+            .flat_map(|(s, v)| s.subtract(&set).into_iter().map(|s| (s, *v)))
+            .collect();
+        self.writes.push((set, val));
+    }
+
+    pub fn get(&self, addr: u128) -> Option<u128> {
+        self.writes
+            .iter()
+            .find(|(s, _)| (addr ^ s.fixed) & !s.float == 0)
+            .map(|(_, v)| *v)
+    }
+
+    /// The number of addresses that have been written to, or `None` if that
+    /// doesn't fit in a `u128`.
+    pub fn len(&self) -> Option<u128> {
+        self.writes.iter().try_fold(0u128, |acc, (s, _)| acc.checked_add(s.len()?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Every written address with its value, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item=(u128, u128)> + '_ {
+        self.writes.iter().flat_map(|(s, v)| {
+            // walks the subsets of the floating bits
+            let mut sub = Some(0u128);
+            std::iter::from_fn(move || {
+                let cur = sub?;
+                let next = cur.wrapping_sub(s.float) & s.float;
+                sub = if next == 0 { None } else { Some(next) };
+                Some((s.fixed | cur, *v))
+            })
+        })
+    }
+
+    /// The sum of every value in memory, or `None` if it doesn't fit.
+    pub fn sum(&self) -> Option<u128> {
+        self.writes
+            .iter()
+            .try_fold(0u128, |acc, (s, v)| acc.checked_add(s.len()?.checked_mul(*v)?))
+    }
+}
+
+struct VM {
+    mem: HashMap<u128, u128>,
+    mem_v2: FloatingMemory,
+    mask: Mask,
+}
+
+impl VM {
+    fn new() -> Self {
+        Self {
+            mem: HashMap::new(),
+            mem_v2: FloatingMemory::default(),
+            mask: Mask::default(),
+        }
+    }
+    fn process(&mut self, inst: &Instruction) {
+        match inst {
+            Instruction::SetMask(m) => { self.mask = m.clone(); },
+            Instruction::SetMem(loc, val) => {
+                self.mem.insert(*loc, (*val | self.mask.or) & self.mask.and);
+            },
+        }
+    }
+    fn process_v2(&mut self, inst: &Instruction) {
+        match inst {
+            Instruction::SetMask(m) => { self.mask = m.clone(); },
+            Instruction::SetMem(loc, val) => {
+                self.mem_v2.write(&self.mask, *loc, *val);
+            },
+        }
+    }
+}
+
+/// The contents of memory after running a program, sorted by address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryImage(pub BTreeMap<u128, u128>);
+
+impl MemoryImage {
+    /// The memory written by the v1 decoder, which masks values.
+    pub fn v1(program: &[Instruction]) -> Self {
+        let mut vm = VM::new();
+        program.iter().for_each(|i| vm.process(i));
+        MemoryImage(vm.mem.into_iter().collect())
+    }
+
+    /// The memory written by the v2 decoder, which masks addresses. Fails if
+    /// more than `limit` addresses were written.
+    pub fn v2(program: &[Instruction], limit: usize) -> Result<Self> {
+        let mut vm = VM::new();
+        program.iter().for_each(|i| vm.process_v2(i));
+        match vm.mem_v2.len() {
+            Some(n) if n <= limit as u128 => Ok(MemoryImage(vm.mem_v2.iter().collect())),
+            n => Err(Error::no_solution(format!(
+                "the program writes to {} addresses, more than the limit of {limit}",
+                n.map_or("over 2^128".into(), |n| n.to_string()),
+            ))),
+        }
+    }
+
+    /// `address,value` records, with a header line.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("address,value\n");
+        for (a, v) in &self.0 {
+            writeln!(out, "{a},{v}").unwrap();
+        }
+        out
+    }
+
+    /// A JSON array of `{"address": A, "value": V}` objects. The numbers are
+    /// written out in full even past 2^53.
+    pub fn to_json(&self) -> String {
+        let records: Vec<Value> = self.0.iter()
+            .map(|(a, v)| json!({"address": a, "value": v}))
+            .collect();
+        serde_json::to_string_pretty(&records).unwrap() + "\n"
+    }
+
+    /// The addresses whose contents differ between `self` and `other`, with
+    /// the value each has there (`None` if it was never written).
+    pub fn diff(&self, other: &MemoryImage) -> MemoryDiff {
+        let mut out = BTreeMap::new();
+        for (a, v) in &self.0 {
+            if other.0.get(a) != Some(v) {
+                out.insert(*a, (Some(*v), other.0.get(a).copied()));
+            }
+        }
+        for (a, v) in &other.0 {
+            if !self.0.contains_key(a) {
+                out.insert(*a, (None, Some(*v)));
+            }
+        }
+        MemoryDiff(out)
+    }
+}
+
+/// The differences between two memory images, by address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryDiff(pub BTreeMap<u128, (Option<u128>, Option<u128>)>);
+
+impl MemoryDiff {
+    /// `address,left,right` records, with a header line; a value that was
+    /// never written is left empty.
+    pub fn to_csv(&self, left: &str, right: &str) -> String {
+        let opt = |v: &Option<u128>| v.map_or(String::new(), |v| v.to_string());
+        let mut out = format!("address,{left},{right}\n");
+        for (a, (l, r)) in &self.0 {
+            writeln!(out, "{a},{},{}", opt(l), opt(r)).unwrap();
+        }
+        out
+    }
+
+    /// A JSON array of `{"address": A, left: L, right: R}` objects, with
+    /// `null` for a value that was never written.
+    pub fn to_json(&self, left: &str, right: &str) -> String {
+        let records: Vec<Value> = self.0.iter()
+            .map(|(a, (l, r))| {
+                let mut record = Map::new();
+                record.insert("address".into(), json!(a));
+                record.insert(left.into(), json!(l));
+                record.insert(right.into(), json!(r));
+                Value::Object(record)
+            })
+            .collect();
+        serde_json::to_string_pretty(&records).unwrap() + "\n"
+    }
+}
+
+fn part1(input: &[Instruction]) -> Result<u128> {
+    let mut vm = VM::new();
+    for i in input {
+        vm.process(i);
+    }
+    vm.mem.values()
+        .try_fold(0u128, |acc, v| acc.checked_add(*v))
+        .ok_or_else(|| Error::no_solution("the sum of the memory is too large"))
+}
+
+fn part2(input: &[Instruction]) -> Result<u128> {
+    let mut vm = VM::new();
+    for i in input {
+        vm.process_v2(i);
+    }
+    vm.mem_v2.sum().ok_or_else(|| Error::no_solution("the sum of the memory is too large"))
+}
+
+/// Parses the program, checking that each write's address and value fit in
+/// the width of the mask in effect at the time.
+fn parse_program(input: &str) -> Result<Vec<Instruction>> {
+    let mut mask = Mask::default();
+    numbered_lines(input).map(|(n, line)| {
+        let inst = line.parse::<Instruction>().map_err(|e| e.at_line(n))?;
+        match &inst {
+            Instruction::SetMask(m) => { mask = m.clone(); },
+            Instruction::SetMem(loc, val) => {
+                let width = mask.width();
+                if !mask.fits(*loc) {
+                    return Err(Error::parse(5, format!("address {loc} doesn't fit in {width} bits")).at_line(n));
+                }
+                if !mask.fits(*val) {
+                    let col = line.find(" = ").unwrap() + 4;
+                    return Err(Error::parse(col, format!("value {val} doesn't fit in {width} bits")).at_line(n));
+                }
+            },
+        }
+        Ok(inst)
+    }).collect()
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+    type Input = Vec<Instruction>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_program(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day14_test() {
+        let instr = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X";
+        assert_eq!(
+            instr.parse::<Instruction>(),
+            Ok(Instruction::SetMask(Mask { or: 64, and: !2, float: 0xFFFFFFFBD, width: 36 }))
+        );
+        let instr = "mem[7] = 101";
+        assert_eq!(
+            instr.parse::<Instruction>(),
+            Ok(Instruction::SetMem(7, 101)),
+        );
+        assert_eq!(
+            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX2X".parse::<Instruction>(),
+            Err(Error::parse(42, "unexpected mask character '2'")),
+        );
+
+        let input = parse_program("\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0
+").unwrap();
+        assert_eq!(part1(&input), Ok(165));
+        let input = parse_program("\
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+").unwrap();
+        assert_eq!(part2(&input), Ok(208));
+
+        let input = parse_program("\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX
+mem[0] = 3
+mask = 0000XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX1
+mem[0] = 1
+mask = 000000000000000000000000000000000000
+mem[5] = 7
+").unwrap();
+        assert_eq!(part2(&input), Ok(3 * (1 << 36) - 2 * (1 << 31) - 1 + 7));
+        let mut vm = VM::new();
+        input.iter().for_each(|i| vm.process_v2(i));
+        assert_eq!(vm.mem_v2.len(), Some(1 << 36));
+        assert_eq!(vm.mem_v2.get(5), Some(7));
+        assert_eq!(vm.mem_v2.get(9), Some(1));
+        assert_eq!(vm.mem_v2.get(1 << 35 | 1), Some(3));
+
+        let program = parse_program("mask = 0X1X\nmem[2] = 3\nmem[9] = 0\n").unwrap();
+        let v1 = MemoryImage::v1(&program);
+        let v2 = MemoryImage::v2(&program, 100).unwrap();
+        assert_eq!(v1.to_csv(), "address,value\n2,3\n9,2\n");
+        let json: Value = serde_json::from_str(&v1.to_json()).unwrap();
+        assert_eq!(json, json!([{"address": 2, "value": 3}, {"address": 9, "value": 2}]));
+        assert_eq!(v2.to_csv(), "address,value\n2,3\n3,3\n6,3\n7,3\n10,0\n11,0\n14,0\n15,0\n");
+        let diff = v1.diff(&v2);
+        assert_eq!(diff.to_csv("v1", "v2"), "address,v1,v2\n3,,3\n6,,3\n7,,3\n9,2,\n10,,0\n11,,0\n14,,0\n15,,0\n");
+        let json: Value = serde_json::from_str(&diff.to_json("v1", "v2")).unwrap();
+        assert_eq!(json[3], json!({"address": 9, "v1": 2, "v2": null}));
+        let json: Value = serde_json::from_str(&diff.to_json("a \"b\"", "c")).unwrap();
+        assert_eq!(json[3]["a \"b\""], json!(2));
+        let big = MemoryImage([(u128::MAX, 1u128 << 100)].into_iter().collect());
+        assert_eq!(
+            big.to_json(),
+            format!("[\n  {{\n    \"address\": {},\n    \"value\": {}\n  }}\n]\n", u128::MAX, 1u128 << 100),
+        );
+        assert_eq!(MemoryImage::default().to_json(), "[]\n");
+        assert!(MemoryImage::v2(&program, 3).is_err());
+
+        let input = parse_program(&format!("mask = 1{}\nmem[3] = {}\n", "X".repeat(127), u128::MAX)).unwrap();
+        assert_eq!(part1(&input), Ok(u128::MAX));
+        assert_eq!(part2(&input), Err(Error::no_solution("the sum of the memory is too large")));
+        let input = parse_program("mask = 1X0X\nmem[5] = 2\nmask = 0X00X\nmem[3] = 1\n").unwrap();
+        assert_eq!(part1(&input), Ok(8 + 1));
+        assert_eq!(part2(&input), Ok(12));
+        assert_eq!(
+            parse_program("mask = 1X0X\nmem[5] = 16\n"),
+            Err(Error::Parse { line: 2, column: 10, message: "value 16 doesn't fit in 4 bits".into() }),
+        );
+        assert_eq!(
+            parse_program("mem[68719476736] = 1\n"),
+            Err(Error::Parse { line: 1, column: 5, message: "address 68719476736 doesn't fit in 36 bits".into() }),
+        );
+        assert_eq!(
+            "mask = ".parse::<Instruction>(),
+            Err(Error::parse(8, "mask must be 1 to 128 bits wide, not 0")),
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+use crate::input::field;
+use crate::{Error, Result, Solver};
+
+fn doit(starting: &[usize], target: usize) -> usize {
+    let mut turn = 1usize;
+    let mut last = 0;
+    // every number spoken after the starting ones is an age, so smaller
+    // than the turn count; only starting numbers can be bigger, and those
+    // go in `big` rather than sizing `arr` after them
+    let size = target.max(starting.len()) + 1;
+    let mut arr: Vec<(usize,usize)> = Vec::with_capacity(size);
+    for _ in 0..size { arr.push((0,0)); }
+    let mut big: HashMap<usize, (usize,usize)> = HashMap::new();
+    for s in starting {
+        if *s < size { arr[*s] = (0, turn); } else { big.insert(*s, (0, turn)); }
+        last = *s;
+        turn += 1;
+    }
+    while turn <= target {
+        let prev = if last < size { arr[last] } else { big[&last] };
+        let next = if prev.0 == 0 { 0 } else { prev.1 - prev.0 };
+
+        arr[next] = (arr[next].1, turn);
+
+        last = next;
+        turn += 1;
+    }
+    last
+}
+
+fn part1(input: &[usize]) -> usize {
+    doit(input, 2020)
+}
+
+fn part2(input: &[usize]) -> usize {
+    doit(input, 30000000)
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let line = input.trim();
+        if line.is_empty() {
+            return Err(Error::parse(1, "no starting numbers").at_line(1));
+        }
+        let mut offset = 0;
+        line.split(',')
+            .map(|s| {
+                let n = field(s, offset).map_err(|e| e.at_line(1));
+                offset += s.len() + 1;
+                n
+            })
+            .collect()
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(Ok(part2(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day15_test() {
+        let input = &[0, 3, 6];
+        assert_eq!(part1(input), 436);
+        assert_eq!(part2(input), 175594);
+        let input = &[1, 3, 2];
+        assert_eq!(part1(input), 1);
+        let input = &[2, 1, 3];
+        assert_eq!(part1(input), 10);
+        let input = &[1, 2, 3];
+        assert_eq!(part1(input), 27);
+        let input = &[2, 3, 1];
+        assert_eq!(part1(input), 78);
+        let input = &[3, 2, 1];
+        assert_eq!(part1(input), 438);
+        let input = &[3, 1, 2];
+        assert_eq!(part1(input), 1836);
+        // a starting number that's never spoken again doesn't change the game
+        assert_eq!(part1(&[usize::MAX, 3, 6]), part1(&[4000, 3, 6]));
+
+        assert_eq!(
+            Day15::parse("3,1,a\n").err(),
+            Some(Error::Parse { line: 1, column: 5, message: "\"a\": invalid digit found in string".into() }),
+        );
+    }
+}
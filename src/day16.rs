@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::vec::Vec;
+use std::str::FromStr;
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::input::{eof_line, field, numbered_groups, parse_lines};
+use crate::{Error, Result, Solver};
+
+#[derive(Clone, Debug)]
+pub struct Rule {
+    name: String,
+    valid: [Range<u64>; 2],
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^([^:]+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
+        }
+        let caps = RE.captures(s)
+            .ok_or(Error::parse(1, "expected \"<name>: <a>-<b> or <c>-<d>\""))?;
+        let num = |i| {
+            let m = caps.get(i).unwrap();
+            field::<u64>(m.as_str(), m.start())
+        };
+        Ok(Self {
+            name: caps.get(1).unwrap().as_str().into(),
+            valid: [num(2)? .. num(3)? + 1, num(4)? .. num(5)? + 1],
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Ticket {
+    vals: Vec<u64>,
+}
+
+impl FromStr for Ticket {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut offset = 0;
+        let vals = s.split(',')
+            .map(|s| {
+                let n = field::<u64>(s, offset);
+                offset += s.len() + 1;
+                n
+            })
+            .collect::<Result<_>>()?;
+        Ok(Ticket { vals })
+    }
+}
+
+pub struct Input {
+    rules: Vec<Rule>,
+    my_ticket: Ticket,
+    tickets: Vec<Ticket>,
+}
+
+/// Checks a section's header line and returns the lines following it.
+fn section<'a>(group: Option<&[(usize, &'a str)]>, header: &str, eof: usize) -> Result<Vec<(usize, &'a str)>> {
+    match group {
+        Some([(_, line), rest @ ..]) if *line == header => Ok(rest.to_vec()),
+        Some([(n, _), ..]) => Err(Error::parse(1, format!("expected \"{header}\"")).at_line(*n)),
+        _ => Err(Error::parse(1, format!("missing \"{header}\" section")).at_line(eof)),
+    }
+}
+
+fn setup(input: &str) -> Result<Input> {
+    let groups = numbered_groups(input);
+    let eof = eof_line(input);
+    let rules = parse_lines(groups.first().cloned().unwrap_or_default())?;
+    let mine = section(groups.get(1).map(|g| g.as_slice()), "your ticket:", eof)?;
+    let (n, line) = *mine.first()
+        .ok_or(Error::parse(1, "missing your ticket").at_line(eof))?;
+    let my_ticket = line.parse::<Ticket>().map_err(|e| e.at_line(n))?;
+    let nearby = section(groups.get(2).map(|g| g.as_slice()), "nearby tickets:", eof)?;
+    let tickets: Vec<Ticket> = parse_lines(nearby.iter().copied())?;
+    if let Some(((n, _), t)) = nearby.iter().zip(&tickets).find(|(_, t)| t.vals.len() != my_ticket.vals.len()) {
+        return Err(Error::parse(1, format!(
+            "ticket has {} fields, expected {}", t.vals.len(), my_ticket.vals.len(),
+        )).at_line(*n));
+    }
+    Ok(Input {
+        rules,
+        my_ticket,
+        tickets,
+    })
+}
+
+fn part1(input: &Input) -> u64 {
+    let valid_ranges: Vec<Range<u64>> = input.rules
+        .iter()
+        .flat_map(|rule| rule.valid.iter())
+        .cloned()
+        .collect();
+    input.tickets
+        .iter()
+        .flat_map(|row| row.vals.iter())
+        .filter(|val| !valid_ranges.iter().any(|r| r.contains(val)))
+        .sum()
+}
+
+fn find_ticket_values(input: &Input) -> Result<HashMap<&String, u64>> {
+    let valid_ranges: Vec<Range<u64>> = input.rules
+        .iter()
+        .flat_map(|rule| rule.valid.iter())
+        .cloned()
+        .collect();
+    let tickets: Vec<Ticket> = input.tickets
+        .iter()
+        .filter(|row| row.vals.iter().all(|val|
+            valid_ranges.iter().any(|r| r.contains(val))
+        ))
+        .cloned()
+        .collect();
+
+    let mut columns: Vec<HashSet<u64>> = Vec::with_capacity(input.my_ticket.vals.len());
+    for _ in 0..input.my_ticket.vals.len() {
+        columns.push(HashSet::new());
+    }
+
+    tickets
+        .iter()
+        .flat_map(|row| row.vals.iter().enumerate())
+        .for_each(|(idx, val)| {
+            columns[idx].insert(*val);
+        });
+
+    let mut matches:Vec<(&String, Vec<usize>)> = input.rules
+        .iter()
+        .map(|r| {
+            (
+                &r.name,
+                columns.iter()
+                    .enumerate()
+                    .filter(|(_, set)|
+                        set.iter()
+                        .all(|v| r.valid[0].contains(v) || r.valid[1].contains(v))
+                    )
+                    .map(|(idx, _)| idx)
+                    .collect::<Vec<usize>>(),
+            )
+        })
+        .collect();
+
+    let mut eliminated: HashSet<usize> = HashSet::new();
+    while let Some(n) = matches.iter()
+            .find(|(_, v)| v.len() == 1 && !eliminated.contains(&v[0]))
+            .map(|(_, v)| v[0]) {
+        for (_, v) in matches.iter_mut() {
+            if v.len() > 1 {
+                if let Ok(idx) = v.binary_search(&n) {
+                    v.splice(idx..=idx, []);
+                }
+            }
+        }
+        eliminated.insert(n);
+    }
+    if let Some((name, _)) = matches.iter().find(|(_, v)| v.len() != 1) {
+        return Err(Error::no_solution(format!("can't tell which field is {name}")));
+    }
+    Ok(matches.into_iter().map(|(k, v)| (k, input.my_ticket.vals[v[0]])).collect())
+}
+
+fn part2(input: &Input) -> Result<u64> {
+    Ok(find_ticket_values(input)?
+        .iter()
+        .filter(|(n,_)| n.starts_with("departure"))
+        .map(|(_,v)| v)
+        .product())
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u8 = 16;
+    type Input = Input;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        setup(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day16_test() {
+        let input = setup(include_str!("day16.testinput")).unwrap();
+        assert_eq!(part1(&input), 71);
+
+        let input = setup(include_str!("day16.testinput2")).unwrap();
+        let m = find_ticket_values(&input);
+        assert_eq!(m, Ok(HashMap::from_iter([
+                (&"class".to_string(), 12),
+                (&"row".to_string(), 11),
+                (&"seat".to_string(), 13),
+        ])));
+
+        let input = include_str!("day16.testinput2").replace("15,1,5", "15,1");
+        assert_eq!(
+            setup(&input).err(),
+            Some(Error::Parse { line: 10, column: 1, message: "ticket has 2 fields, expected 3".into() }),
+        );
+    }
+}
@@ -0,0 +1,585 @@
+use std::fmt;
+use std::str::FromStr;
+use std::vec::Vec;
+use num_bigint::{BigInt, Sign};
+
+use crate::input::numbered_lines;
+use crate::{Error, Result, Solver};
+
+/// The most bits a `^` in [`BinOp::apply_big`] may produce, so that a small
+/// expression can't run out of memory.
+pub const MAX_POW_BITS: u64 = 1 << 20;
+
+/// How deep a parsed expression may be, counting parentheses, unary minus
+/// and chained operators, so that parsing and evaluating a line can't
+/// overflow the stack.
+pub const MAX_NESTING: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl BinOp {
+    pub const ALL: [BinOp; 6] = [BinOp::Add, BinOp::Sub, BinOp::Mul, BinOp::Div, BinOp::Rem, BinOp::Pow];
+
+    pub fn symbol(&self) -> char {
+        match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+            BinOp::Rem => '%',
+            BinOp::Pow => '^',
+        }
+    }
+
+    fn from_char(c: char) -> Option<BinOp> {
+        BinOp::ALL.into_iter().find(|op| op.symbol() == c)
+    }
+
+    /// Applies the operator, or returns a description of why it can't be
+    /// done: overflow, dividing by zero or a negative exponent.
+    pub fn apply(&self, l: i64, r: i64) -> Result<i64, String> {
+        let v = match self {
+            BinOp::Add => l.checked_add(r),
+            BinOp::Sub => l.checked_sub(r),
+            BinOp::Mul => l.checked_mul(r),
+            BinOp::Div | BinOp::Rem if r == 0 => { return Err("division by zero".into()); },
+            BinOp::Div => l.checked_div(r),
+            BinOp::Rem => l.checked_rem(r),
+            BinOp::Pow => {
+                let Ok(r) = u32::try_from(r) else {
+                    return Err(format!("negative exponent in {l} ^ {r}"));
+                };
+                l.checked_pow(r)
+            },
+        };
+        v.ok_or_else(|| format!("overflow in {l} {} {r}", self.symbol()))
+    }
+
+    /// Like [`BinOp::apply`], but can't overflow. A power whose result would
+    /// be over [`MAX_POW_BITS`] bits is refused.
+    pub fn apply_big(&self, l: &BigInt, r: &BigInt) -> Result<BigInt, String> {
+        Ok(match self {
+            BinOp::Add => l + r,
+            BinOp::Sub => l - r,
+            BinOp::Mul => l * r,
+            BinOp::Div | BinOp::Rem if r.sign() == Sign::NoSign => { return Err("division by zero".into()); },
+            BinOp::Div => l / r,
+            BinOp::Rem => l % r,
+            BinOp::Pow => {
+                if r.sign() == Sign::Minus {
+                    return Err(format!("negative exponent {r}"));
+                }
+                // |l| <= 1 stays small however large the exponent is
+                if l.bits() > 1 && u64::try_from(r).map_or(true, |e| (l.bits() - 1).saturating_mul(e) > MAX_POW_BITS) {
+                    return Err(format!("a {}-bit number to the power {r} has more than {MAX_POW_BITS} bits", l.bits()));
+                }
+                match u32::try_from(r) {
+                    Ok(e) => l.pow(e),
+                    // only reachable for |l| <= 1
+                    Err(_) => if l.sign() == Sign::Minus && r.bit(0) { l.clone() } else { l * l },
+                }
+            },
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+/// Operator precedence and associativity, built from the loosest-binding
+/// level to the tightest. Operators that aren't in the table are rejected
+/// by the parser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Precedence {
+    levels: Vec<(Assoc, Vec<BinOp>)>,
+    unary: Option<usize>,
+}
+
+impl Precedence {
+    pub fn new() -> Self {
+        Precedence { levels: Vec::new(), unary: None }
+    }
+
+    /// Adds a level that binds tighter than all of the ones before it.
+    pub fn level(mut self, assoc: Assoc, ops: &[BinOp]) -> Self {
+        self.levels.push((assoc, ops.to_vec()));
+        self
+    }
+
+    /// Makes unary minus bind tighter than the levels added so far and
+    /// looser than the ones added after. Without this it binds tightest.
+    pub fn unary(mut self) -> Self {
+        self.unary = Some(self.levels.len());
+        self
+    }
+
+    /// The rules for part 1: everything but `^` evaluates left to right.
+    pub fn left_to_right() -> Self {
+        use BinOp::*;
+        Precedence::new()
+            .level(Assoc::Left, &[Add, Sub, Mul, Div, Rem])
+            .unary()
+            .level(Assoc::Right, &[Pow])
+    }
+
+    /// The rules for part 2: addition and subtraction come before
+    /// multiplication and division.
+    pub fn addition_first() -> Self {
+        use BinOp::*;
+        Precedence::new()
+            .level(Assoc::Left, &[Mul, Div, Rem])
+            .level(Assoc::Left, &[Add, Sub])
+            .unary()
+            .level(Assoc::Right, &[Pow])
+    }
+
+    /// The usual rules.
+    pub fn standard() -> Self {
+        use BinOp::*;
+        Precedence::new()
+            .level(Assoc::Left, &[Add, Sub])
+            .level(Assoc::Left, &[Mul, Div, Rem])
+            .unary()
+            .level(Assoc::Right, &[Pow])
+    }
+
+    fn get(&self, op: BinOp) -> Option<(usize, Assoc)> {
+        self.levels
+            .iter()
+            .enumerate()
+            .find(|(_, (_, ops))| ops.contains(&op))
+            .map(|(i, (assoc, _))| (i, *assoc))
+    }
+
+    fn ops(&self) -> impl Iterator<Item=BinOp> + '_ {
+        self.levels.iter().flat_map(|(_, ops)| ops.iter().copied())
+    }
+
+    fn unary_level(&self) -> usize {
+        self.unary.unwrap_or(self.levels.len())
+    }
+}
+
+impl Default for Precedence {
+    fn default() -> Self {
+        Precedence::standard()
+    }
+}
+
+/// A parsed expression. `at` is the 1-based column of the operator or
+/// literal, for reporting errors found while evaluating. Parentheses aren't kept; the
+/// grouping is in the shape of the tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Num(i64),
+    /// A literal too large for an `i64`, which only [`Expr::eval_big`] can
+    /// evaluate.
+    BigNum { at: usize, n: BigInt },
+    Neg { at: usize, e: Box<Expr> },
+    Bin { op: BinOp, at: usize, l: Box<Expr>, r: Box<Expr> },
+}
+
+impl Expr {
+    pub fn eval(&self) -> Result<i64> {
+        match self {
+            Expr::Num(n) => Ok(*n),
+            Expr::BigNum { at, n } => Err(Error::parse(*at, format!("{n} doesn't fit in 64 bits"))),
+            Expr::Neg { at, e } => {
+                let v = e.eval()?;
+                v.checked_neg().ok_or_else(|| Error::parse(*at, format!("overflow in -({v})")))
+            },
+            Expr::Bin { op, at, l, r } => op.apply(l.eval()?, r.eval()?).map_err(|e| Error::parse(*at, e)),
+        }
+    }
+
+    /// Evaluates the expression exactly, without overflow.
+    pub fn eval_big(&self) -> Result<BigInt> {
+        self.big(&mut 0)
+    }
+
+    /// The most bits needed to hold any value met while evaluating the
+    /// expression, not counting the sign. Results of the checked [`Expr::eval`]
+    /// have to fit in 63.
+    pub fn peak_bits(&self) -> Result<u64> {
+        let mut peak = 0;
+        self.big(&mut peak)?;
+        Ok(peak)
+    }
+
+    fn big(&self, peak: &mut u64) -> Result<BigInt> {
+        let v = match self {
+            Expr::Num(n) => BigInt::from(*n),
+            Expr::BigNum { n, .. } => n.clone(),
+            Expr::Neg { e, .. } => -e.big(peak)?,
+            Expr::Bin { op, at, l, r } => op.apply_big(&l.big(peak)?, &r.big(peak)?).map_err(|e| Error::parse(*at, e))?,
+        };
+        *peak = (*peak).max(v.bits());
+        Ok(v)
+    }
+
+    /// The expression with every operation in parentheses, e.g.
+    /// `((1 + 2) * (-3))`. This is also its `Display` form.
+    pub fn parenthesized(&self) -> String {
+        match self {
+            Expr::Num(n) => n.to_string(),
+            Expr::BigNum { n, .. } => n.to_string(),
+            Expr::Neg { e, .. } => format!("(-{})", e.parenthesized()),
+            Expr::Bin { op, l, r, .. } => format!("({} {} {})", l.parenthesized(), op.symbol(), r.parenthesized()),
+        }
+    }
+
+    /// The expression as an S-expression, e.g. `(* (+ 1 2) (- 3))`.
+    pub fn sexpr(&self) -> String {
+        match self {
+            Expr::Num(n) => n.to_string(),
+            Expr::BigNum { n, .. } => n.to_string(),
+            Expr::Neg { e, .. } => format!("(- {})", e.sexpr()),
+            Expr::Bin { op, l, r, .. } => format!("({} {} {})", op.symbol(), l.sexpr(), r.sexpr()),
+        }
+    }
+
+    /// The expression in reverse Polish notation, e.g. `1 2 + 3 neg *`.
+    /// Unary minus is written `neg` to tell it apart from subtraction.
+    pub fn rpn(&self) -> String {
+        match self {
+            Expr::Num(n) => n.to_string(),
+            Expr::BigNum { n, .. } => n.to_string(),
+            Expr::Neg { e, .. } => format!("{} neg", e.rpn()),
+            Expr::Bin { op, l, r, .. } => format!("{} {} {}", l.rpn(), r.rpn(), op.symbol()),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.parenthesized())
+    }
+}
+
+/// A problem with one line of input: where it is, what the parser would
+/// have accepted there, and the line itself for showing in context.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub source: String,
+    /// The tokens allowed at `column`; empty for errors that aren't about
+    /// syntax, like overflow.
+    pub expected: Vec<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn syntax(column: usize, expected: Vec<String>) -> Self {
+        let message = match expected.split_last() {
+            Some((last, [])) => format!("expected {last}"),
+            Some((last, rest)) => format!("expected {} or {last}", rest.join(", ")),
+            None => "unexpected input".into(),
+        };
+        Diagnostic { line: 0, column, source: String::new(), expected, message }
+    }
+
+    fn from_error(e: Error) -> Self {
+        match e {
+            Error::Parse { line, column, message } =>
+                Diagnostic { line, column, source: String::new(), expected: Vec::new(), message },
+            Error::NoSolution(message) | Error::Io(message) =>
+                Diagnostic { line: 0, column: 1, source: String::new(), expected: Vec::new(), message },
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    /// Shows the message, then the line with a caret under the column:
+    ///
+    /// ```text
+    /// line 3, column 10: expected a number, "(" or "-"
+    ///   3 | 3 * (4 + )
+    ///     |          ^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let w = self.line.to_string().len() + 2;
+        let col = self.source.get(.. self.column - 1).map_or(self.column - 1, |s| s.chars().count());
+        writeln!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "{:>w$} | {}", self.line, self.source)?;
+        write!(f, "{:>w$} | {}^", "", " ".repeat(col))
+    }
+}
+
+impl From<Diagnostic> for Error {
+    fn from(d: Diagnostic) -> Self {
+        Error::Parse { line: d.line, column: d.column, message: d.message }
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+    table: &'a Precedence,
+    depth: usize,
+    nesting: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> Option<char> {
+        self.pos += self.s[self.pos..].len() - self.s[self.pos..].trim_start().len();
+        self.s[self.pos..].chars().next()
+    }
+
+    /// What can follow an operand here: an operator from the table, or
+    /// whatever closes the current group.
+    fn expected_after_operand(&self) -> Vec<String> {
+        let mut out: Vec<String> = self.table.ops().map(|op| format!("{:?}", op.symbol().to_string())).collect();
+        out.push(if self.depth > 0 { "\")\"".into() } else { "end of line".into() });
+        out
+    }
+
+    fn operand(&mut self) -> Result<Expr, Diagnostic> {
+        match self.peek() {
+            Some('-') => {
+                let at = self.pos + 1;
+                self.pos += 1;
+                Ok(Expr::Neg { at, e: Box::new(self.expr(self.table.unary_level())?) })
+            },
+            Some('(') => {
+                self.pos += 1;
+                self.depth += 1;
+                let v = self.expr(0)?;
+                if self.peek() != Some(')') {
+                    return Err(Diagnostic::syntax(self.pos + 1, self.expected_after_operand()));
+                }
+                self.pos += 1;
+                self.depth -= 1;
+                Ok(v)
+            },
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.pos;
+                self.pos = self.s[start..].find(|c: char| !c.is_ascii_digit()).map_or(self.s.len(), |i| start + i);
+                // only digits, so this can't fail
+                let n = BigInt::from_str(&self.s[start..self.pos]).unwrap();
+                Ok(match i64::try_from(&n) {
+                    Ok(n) => Expr::Num(n),
+                    Err(_) => Expr::BigNum { at: start + 1, n },
+                })
+            },
+            _ => Err(Diagnostic::syntax(self.pos + 1, vec!["a number".into(), "\"(\"".into(), "\"-\"".into()])),
+        }
+    }
+
+    /// Goes one level deeper, or fails if that's past [`MAX_NESTING`].
+    fn nest(&mut self) -> Result<(), Diagnostic> {
+        if self.nesting == MAX_NESTING {
+            let message = format!("expression nested more than {MAX_NESTING} deep");
+            return Err(Diagnostic { line: 0, column: self.pos + 1, source: String::new(), expected: Vec::new(), message });
+        }
+        self.nesting += 1;
+        Ok(())
+    }
+
+    /// Parses operators at level `min` or tighter.
+    fn expr(&mut self, min: usize) -> Result<Expr, Diagnostic> {
+        let outer = self.nesting;
+        self.peek();
+        self.nest()?;
+        let mut l = self.operand()?;
+        while let Some(c) = self.peek() {
+            let Some((op, (level, assoc))) = BinOp::from_char(c).and_then(|op| Some((op, self.table.get(op)?))) else {
+                if c == ')' && self.depth > 0 {
+                    break;
+                }
+                let mut d = Diagnostic::syntax(self.pos + 1, self.expected_after_operand());
+                if BinOp::from_char(c).is_some() {
+                    d.message = format!("operator {c} isn't in the precedence table");
+                }
+                return Err(d);
+            };
+            if level < min {
+                break;
+            }
+            // each operator makes `l` one level deeper
+            self.nest()?;
+            let at = self.pos + 1;
+            self.pos += 1;
+            let r = self.expr(if assoc == Assoc::Left { level + 1 } else { level })?;
+            l = Expr::Bin { op, at, l: Box::new(l), r: Box::new(r) };
+        }
+        self.nesting = outer;
+        Ok(l)
+    }
+}
+
+/// Parses `s` using the precedence rules in `table`.
+pub fn parse(s: &str, table: &Precedence) -> Result<Expr> {
+    Ok(Parser { s, pos: 0, table, depth: 0, nesting: 0 }.expr(0)?)
+}
+
+/// Evaluates `s` using the precedence rules in `table`.
+pub fn eval(s: &str, table: &Precedence) -> Result<i64> {
+    parse(s, table)?.eval()
+}
+
+/// Evaluates `s` exactly using the precedence rules in `table`.
+pub fn eval_big(s: &str, table: &Precedence) -> Result<BigInt> {
+    parse(s, table)?.eval_big()
+}
+
+/// The exact sum of every line under `table`.
+pub fn sum_big(input: &[(usize, String)], table: &Precedence) -> Result<BigInt> {
+    input
+        .iter()
+        .map(|(n, row)| eval_big(row, table).map_err(|e| e.at_line(*n)))
+        .sum()
+}
+
+fn eval_line(line: usize, s: &str, table: &Precedence) -> Result<i64, Diagnostic> {
+    Parser { s, pos: 0, table, depth: 0, nesting: 0 }
+        .expr(0)
+        .and_then(|e| e.eval().map_err(Diagnostic::from_error))
+        .map_err(|d| Diagnostic { line, source: s.into(), ..d })
+}
+
+/// Checks every line, returning a diagnostic for each one that can't be
+/// parsed or evaluated under `table`.
+pub fn diagnose(input: &[(usize, String)], table: &Precedence) -> Vec<Diagnostic> {
+    input
+        .iter()
+        .filter_map(|(n, row)| eval_line(*n, row, table).err())
+        .collect()
+}
+
+fn sum(input: &[(usize, String)], table: &Precedence) -> Result<i64> {
+    let values: Vec<_> = input.iter().map(|(n, row)| eval_line(*n, row, table)).collect();
+    let mut errors = values.iter().filter_map(|v| v.as_ref().err());
+    if let Some(first) = errors.next() {
+        let more = errors.count();
+        let mut e = first.clone();
+        if more > 0 {
+            e.message = format!("{} (and {more} more {})", e.message, if more == 1 { "error" } else { "errors" });
+        }
+        return Err(e.into());
+    }
+    values
+        .into_iter()
+        .try_fold(0i64, |acc, v| acc.checked_add(v.unwrap()))
+        .ok_or_else(|| Error::no_solution("the sum is too large"))
+}
+
+fn part1(input: &[(usize, String)]) -> Result<i64> {
+    sum(input, &Precedence::left_to_right())
+}
+
+fn part2(input: &[(usize, String)]) -> Result<i64> {
+    sum(input, &Precedence::addition_first())
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<(usize, String)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(numbered_lines(input).map(|(n, l)| (n, l.into())).collect())
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day18_test() {
+        let p1 = Precedence::left_to_right();
+        assert_eq!(eval("8 + 9", &p1), Ok(17));
+        assert_eq!(eval("8 * 9", &p1), Ok(72));
+        assert_eq!(eval("1 + 2 * 3 + 4 * 5 + 6", &p1), Ok(71));
+        assert_eq!(eval("1 + (2 * 3) + (4 * (5 + 6))", &p1), Ok(51));
+        assert_eq!(eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", &p1), Ok(13632));
+
+        let p2 = Precedence::addition_first();
+        assert_eq!(eval("1 + 2 * 3 + 4 * 5 + 6", &p2), Ok(231));
+        assert_eq!(eval("1 + (2 * 3) + (4 * (5 + 6))", &p2), Ok(51));
+        assert_eq!(eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", &p2), Ok(23340));
+
+        let std = Precedence::standard();
+        assert_eq!(eval("10 - 4 - 3", &std), Ok(3));
+        assert_eq!(eval("2 ^ 3 ^ 2", &std), Ok(512));
+        assert_eq!(eval("-2 ^ 2 + 17 % 5 * 3 / 2", &std), Ok(-1));
+        assert_eq!(eval("--3 * -(1 - 3)", &std), Ok(6));
+        let right = Precedence::new().level(Assoc::Right, &[BinOp::Sub]);
+        assert_eq!(eval("10 - 4 - 3", &right), Ok(9));
+        assert_eq!(eval("1 + 2", &right), Err(Error::parse(3, "operator + isn't in the precedence table")));
+
+        assert_eq!(eval("9223372036854775807 + 1", &std), Err(Error::parse(21, "overflow in 9223372036854775807 + 1")));
+        assert_eq!(eval("(1 + 2) / (3 - 3)", &std), Err(Error::parse(9, "division by zero")));
+        assert_eq!(eval("2 ^ -1", &std), Err(Error::parse(3, "negative exponent in 2 ^ -1")));
+        assert_eq!(eval("(1 + 2", &std), Err(Error::parse(7, "expected \"+\", \"-\", \"*\", \"/\", \"%\", \"^\" or \")\"")));
+        assert_eq!(eval("1 2", &right), Err(Error::parse(3, "expected \"-\" or end of line")));
+
+        let line = "1 + 2 * 3 + -4";
+        let (e1, e2) = (parse(line, &p1).unwrap(), parse(line, &p2).unwrap());
+        assert_eq!(e1.to_string(), "(((1 + 2) * 3) + (-4))");
+        assert_eq!(e2.to_string(), "((1 + 2) * (3 + (-4)))");
+        assert_eq!(e1.sexpr(), "(+ (* (+ 1 2) 3) (- 4))");
+        assert_eq!(e2.rpn(), "1 2 + 3 4 neg + *");
+        assert_eq!((e1.eval(), e2.eval()), (Ok(5), Ok(-3)));
+
+        let big = "99999999999 * 99999999999 * 99999999999 - -1";
+        assert!(eval(big, &p1).is_err());
+        assert_eq!(eval_big(big, &p1).unwrap().to_string(), "999999999970000000000300000000000");
+        assert_eq!(parse(big, &p1).unwrap().peak_bits(), Ok(110));
+        assert_eq!(parse("2 * 3 ^ 2", &p2).unwrap().peak_bits(), Ok(5));
+        assert_eq!(eval_big("(0 - 2) % 0", &std), Err(Error::parse(9, "division by zero")));
+        let huge = "123456789012345678901234567890";
+        assert_eq!(eval_big(&format!("{huge} - 1"), &std).unwrap().to_string(), "123456789012345678901234567889");
+        assert_eq!(eval(&format!("1 + {huge}"), &std), Err(Error::parse(5, format!("{huge} doesn't fit in 64 bits"))));
+        assert_eq!(
+            eval_big("9 ^ 4000000000", &std),
+            Err(Error::parse(3, format!("a 4-bit number to the power 4000000000 has more than {MAX_POW_BITS} bits"))),
+        );
+        assert_eq!(eval_big(&format!("-1 ^ {huge} + (0 - 1) ^ 4000000001"), &std), Ok(BigInt::from(-2)));
+        assert_eq!(eval_big("2 ^ 1000000", &std).unwrap().bits(), 1000001);
+        let input = Day18::parse("2 ^ 64\n-(2 ^ 64)\n5\n").unwrap();
+        assert_eq!(sum_big(&input, &std), Ok(BigInt::from(5)));
+
+        let input = Day18::parse("1 + 2\n\n3 * (4 + )\n4 / (2 - 2)\n").unwrap();
+        assert_eq!(
+            part1(&input),
+            Err(Error::Parse { line: 3, column: 10, message: "expected a number, \"(\" or \"-\" (and 1 more error)".into() }),
+        );
+        let diags = diagnose(&input, &p2);
+        assert_eq!(diags.iter().map(|d| (d.line, d.column, d.expected.len())).collect::<Vec<_>>(), vec![(3, 10, 3), (4, 3, 0)]);
+        assert_eq!(diags[0].to_string(), "line 3, column 10: expected a number, \"(\" or \"-\"\n  3 | 3 * (4 + )\n    |          ^");
+        assert_eq!(diags[1].message, "division by zero");
+
+        let deep = format!("{}1{}", "(".repeat(10000), ")".repeat(10000));
+        let msg = format!("expression nested more than {MAX_NESTING} deep");
+        assert_eq!(eval(&deep, &std), Err(Error::parse(MAX_NESTING + 1, msg.clone())));
+        assert_eq!(eval(&"-".repeat(10000), &std), Err(Error::parse(MAX_NESTING + 1, msg.clone())));
+        assert_eq!(eval(&["2"; 10000].join(" ^ "), &std), Err(Error::parse(2 * MAX_NESTING + 1, msg.clone())));
+        assert_eq!(eval(&["1"; 10000].join(" + "), &p1), Err(Error::parse(4 * MAX_NESTING - 3, msg)));
+        let shallow = format!("{}1{}", "(".repeat(MAX_NESTING - 1), ")".repeat(MAX_NESTING - 1));
+        assert_eq!(eval(&shallow, &std), Ok(1));
+        let input = Day18::parse(&format!("{deep}\n1 + 1\n")).unwrap();
+        assert_eq!(diagnose(&input, &p1).iter().map(|d| d.line).collect::<Vec<_>>(), vec![1]);
+    }
+}
@@ -0,0 +1,788 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use regex::Regex;
+use std::str::FromStr;
+use std::vec::Vec;
+use crate::input::{eof_line, field, numbered_groups};
+use crate::{Error, Result, Solver};
+
+pub type RuleNum = usize;
+
+#[derive(Clone, Debug)]
+pub enum Rule {
+    AltSeq(Vec<Vec<RuleNum>>),
+    Char(char),
+}
+
+#[derive(Clone, Debug)]
+struct RuleEntry {
+    id: RuleNum,
+    rule: Rule,
+}
+
+impl FromStr for RuleEntry {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, rule) = s.split_once(": ")
+            .ok_or(Error::parse(1, "expected \"<id>: <rule>\""))?;
+        let id = field::<usize>(id, 0)?;
+        let mut offset = s.len() - rule.len();
+        if rule.contains('\"') {
+            let mut chars = rule.chars();
+            match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                (Some('"'), Some(c), Some('"'), None) => Ok(RuleEntry { id, rule: Rule::Char(c) }),
+                _ => Err(Error::parse(offset + 1, "expected a single quoted character")),
+            }
+        }
+        else {
+            let mut seqs: Vec<Vec<RuleNum>> = Vec::new();
+            for sp in rule.split(" | ") {
+                let mut seq = Vec::new();
+                for n in sp.split(' ') {
+                    seq.push(field::<RuleNum>(n, offset)?);
+                    offset += n.len() + 1;
+                }
+                offset += 2;
+                seqs.push(seq);
+            }
+            Ok(RuleEntry { id, rule: Rule::AltSeq(seqs) })
+        }
+    }
+}
+
+fn parse_rules(input: &[(usize, &str)]) -> Result<HashMap<RuleNum, Rule>> {
+    let mut rules = HashMap::new();
+    let mut lines = HashMap::new();
+    for (n, line) in input {
+        let re = line.parse::<RuleEntry>().map_err(|e| e.at_line(*n))?;
+        if lines.insert(re.id, *n).is_some() {
+            return Err(Error::parse(1, format!("rule {} is defined twice", re.id)).at_line(*n));
+        }
+        rules.insert(re.id, re.rule);
+    }
+    if let Some((id, r)) = undefined(&rules).first() {
+        return Err(Error::parse(1, format!("rule {id} refers to undefined rule {r}")).at_line(lines[id]));
+    }
+    Ok(rules)
+}
+
+/// The rules referred to by each rule, in order.
+fn refs(rule: &Rule) -> impl Iterator<Item=&RuleNum> {
+    match rule {
+        Rule::AltSeq(v) => v.as_slice(),
+        Rule::Char(_) => &[],
+    }.iter().flatten()
+}
+
+/// Each reference to an undefined rule, as (rule, undefined rule), sorted.
+fn undefined(rules: &HashMap<RuleNum, Rule>) -> Vec<(RuleNum, RuleNum)> {
+    let mut out: Vec<(RuleNum, RuleNum)> = rules
+        .iter()
+        .flat_map(|(id, rule)| refs(rule).filter(|r| !rules.contains_key(r)).map(|r| (*id, *r)))
+        .collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// Rule `id`, or an error saying it's undefined. `from` is the rule that
+/// refers to it, if any.
+fn lookup(rules: &HashMap<RuleNum, Rule>, id: RuleNum, from: Option<RuleNum>) -> Result<&Rule> {
+    rules.get(&id).ok_or_else(|| Error::no_solution(match from {
+        Some(f) => format!("rule {f} refers to undefined rule {id}"),
+        None => format!("there is no rule {id}"),
+    }))
+}
+
+/// How a message matched a rule: which alternative was taken, and what
+/// each rule in its sequence matched. `Display` writes it as an
+/// S-expression of rule numbers with the characters at the leaves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseTree {
+    Char(RuleNum, char),
+    Node { rule: RuleNum, alt: usize, children: Vec<ParseTree> },
+}
+
+impl fmt::Display for ParseTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTree::Char(_, c) => write!(f, "{c}"),
+            ParseTree::Node { rule, children, .. } => {
+                write!(f, "({rule}")?;
+                for c in children {
+                    write!(f, " {c}")?;
+                }
+                write!(f, ")")
+            },
+        }
+    }
+}
+
+/// An Earley item: alternative `alt` of `rule`, which started at `origin`
+/// and has matched its first `dot` rules so far.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Item {
+    rule: RuleNum,
+    alt: usize,
+    dot: usize,
+    origin: usize,
+}
+
+/// The Earley chart for one message: `sets[i]` holds the items that have
+/// matched up to character `i`. Works for any rules, recursive or not,
+/// since no rule can match the empty string.
+struct Chart<'a> {
+    rules: &'a HashMap<RuleNum, Rule>,
+    chars: Vec<char>,
+    sets: Vec<HashSet<Item>>,
+}
+
+impl<'a> Chart<'a> {
+    fn new(rules: &'a HashMap<RuleNum, Rule>, root: RuleNum, s: &str) -> Self {
+        let chars: Vec<char> = s.chars().collect();
+        let mut sets: Vec<HashSet<Item>> = vec![HashSet::new(); chars.len() + 1];
+        if let Some(Rule::AltSeq(v)) = rules.get(&root) {
+            sets[0].extend((0 .. v.len()).map(|alt| Item { rule: root, alt, dot: 0, origin: 0 }));
+        }
+        for i in 0 ..= chars.len() {
+            let mut queue: Vec<Item> = sets[i].iter().copied().collect();
+            while let Some(item) = queue.pop() {
+                let Some(Rule::AltSeq(v)) = rules.get(&item.rule) else { continue };
+                let new: Vec<Item> = match v[item.alt].get(item.dot) {
+                    // complete: advance whatever was waiting for this rule
+                    None => sets[item.origin]
+                        .iter()
+                        .filter(|w| matches!(rules.get(&w.rule), Some(Rule::AltSeq(wv)) if wv[w.alt].get(w.dot) == Some(&item.rule)))
+                        .map(|w| Item { dot: w.dot + 1, ..*w })
+                        .collect(),
+                    // an undefined rule matches nothing
+                    Some(next) => match rules.get(next) {
+                        None => continue,
+                        Some(Rule::Char(c)) => {
+                            if chars.get(i) == Some(c) {
+                                sets[i + 1].insert(Item { dot: item.dot + 1, ..item });
+                            }
+                            continue;
+                        },
+                        Some(Rule::AltSeq(nv)) => (0 .. nv.len()).map(|alt| Item { rule: *next, alt, dot: 0, origin: i }).collect(),
+                    },
+                };
+                for it in new {
+                    if sets[i].insert(it) {
+                        queue.push(it);
+                    }
+                }
+            }
+        }
+        Chart { rules, chars, sets }
+    }
+
+    fn seq(&self, rule: RuleNum, alt: usize) -> &[RuleNum] {
+        match &self.rules[&rule] {
+            Rule::AltSeq(v) => &v[alt],
+            Rule::Char(_) => &[],
+        }
+    }
+
+    /// Whether alternative `alt` of `rule` matches `chars[start..end]`.
+    fn completed(&self, rule: RuleNum, alt: usize, start: usize, end: usize) -> bool {
+        let dot = self.seq(rule, alt).len();
+        self.sets[end].contains(&Item { rule, alt, dot, origin: start })
+    }
+
+    /// A parse tree for `rule` matching `chars[start..end]`. `path` holds
+    /// the spans being built, so that a rule that can derive itself
+    /// without consuming anything doesn't recurse forever.
+    fn tree(&self, rule: RuleNum, start: usize, end: usize, path: &mut Vec<(RuleNum, usize, usize)>) -> Option<ParseTree> {
+        match &self.rules[&rule] {
+            Rule::Char(c) => (end == start + 1 && self.chars[start] == *c).then_some(ParseTree::Char(rule, *c)),
+            Rule::AltSeq(v) => {
+                if path.contains(&(rule, start, end)) {
+                    return None;
+                }
+                path.push((rule, start, end));
+                let t = (0 .. v.len())
+                    .filter(|alt| self.completed(rule, *alt, start, end))
+                    .find_map(|alt| {
+                        let children = self.children(rule, alt, v[alt].len(), start, end, path)?;
+                        Some(ParseTree::Node { rule, alt, children })
+                    });
+                path.pop();
+                t
+            },
+        }
+    }
+
+    /// Trees for the first `dot` rules of the alternative, which together
+    /// match `chars[start..end]`, found by splitting off the last one.
+    fn children(
+        &self, rule: RuleNum, alt: usize, dot: usize, start: usize, end: usize, path: &mut Vec<(RuleNum, usize, usize)>,
+    ) -> Option<Vec<ParseTree>> {
+        if dot == 0 {
+            return (start == end).then(Vec::new);
+        }
+        let last = self.seq(rule, alt)[dot - 1];
+        (start .. end).rev().find_map(|mid| {
+            let prefix = if dot == 1 { mid == start } else { self.sets[mid].contains(&Item { rule, alt, dot: dot - 1, origin: start }) };
+            if !prefix {
+                return None;
+            }
+            let t = self.tree(last, mid, end, path)?;
+            let mut c = self.children(rule, alt, dot - 1, start, mid, path)?;
+            c.push(t);
+            Some(c)
+        })
+    }
+}
+
+impl Chart<'_> {
+    /// The number of ways `rule` matches `chars[start..end]`, counting no
+    /// higher than 2. `memo` caches the counts for partial alternatives.
+    fn count(
+        &self, rule: RuleNum, start: usize, end: usize,
+        memo: &mut HashMap<(RuleNum, usize, usize, usize, usize), u64>, path: &mut Vec<(RuleNum, usize, usize)>,
+    ) -> u64 {
+        match &self.rules[&rule] {
+            Rule::Char(c) => (end == start + 1 && self.chars[start] == *c) as u64,
+            Rule::AltSeq(v) => {
+                if path.contains(&(rule, start, end)) {
+                    return 0;
+                }
+                path.push((rule, start, end));
+                let mut n = 0;
+                for alt in (0 .. v.len()).filter(|alt| self.completed(rule, *alt, start, end)) {
+                    n = (n + self.count_prefix(rule, alt, v[alt].len(), start, end, memo, path)).min(2);
+                }
+                path.pop();
+                n
+            },
+        }
+    }
+
+    /// The number of ways the first `dot` rules of the alternative match
+    /// `chars[start..end]`, counting no higher than 2.
+    #[allow(clippy::too_many_arguments)]
+    fn count_prefix(
+        &self, rule: RuleNum, alt: usize, dot: usize, start: usize, end: usize,
+        memo: &mut HashMap<(RuleNum, usize, usize, usize, usize), u64>, path: &mut Vec<(RuleNum, usize, usize)>,
+    ) -> u64 {
+        if dot == 0 {
+            return (start == end) as u64;
+        }
+        if let Some(n) = memo.get(&(rule, alt, dot, start, end)) {
+            return *n;
+        }
+        let last = self.seq(rule, alt)[dot - 1];
+        let mut n = 0;
+        for mid in start .. end {
+            let prefix = if dot == 1 { mid == start } else { self.sets[mid].contains(&Item { rule, alt, dot: dot - 1, origin: start }) };
+            if !prefix {
+                continue;
+            }
+            let t = self.count(last, mid, end, memo, path);
+            if t > 0 {
+                n = (n + t * self.count_prefix(rule, alt, dot - 1, start, mid, memo, path)).min(2);
+            }
+        }
+        memo.insert((rule, alt, dot, start, end), n);
+        n
+    }
+}
+
+/// Whether `s` matches rule `root`, for any rules including recursive ones.
+/// Fails if the rules refer to one that isn't defined.
+pub fn matches(rules: &HashMap<RuleNum, Rule>, root: RuleNum, s: &str) -> Result<bool> {
+    check(rules, root)?;
+    Ok(match &rules[&root] {
+        Rule::Char(c) => s.len() == c.len_utf8() && s.starts_with(*c),
+        Rule::AltSeq(v) => {
+            let chart = Chart::new(rules, root, s);
+            (0 .. v.len()).any(|alt| chart.completed(root, alt, 0, chart.chars.len()))
+        },
+    })
+}
+
+/// How `s` matches rule `root`, if it does. When there's more than one
+/// way, this is one of them. Fails like [`matches`].
+pub fn parse_message(rules: &HashMap<RuleNum, Rule>, root: RuleNum, s: &str) -> Result<Option<ParseTree>> {
+    check(rules, root)?;
+    Ok(match &rules[&root] {
+        Rule::Char(c) => (s.len() == c.len_utf8() && s.starts_with(*c)).then_some(ParseTree::Char(root, *c)),
+        Rule::AltSeq(_) => {
+            let chart = Chart::new(rules, root, s);
+            chart.tree(root, 0, chart.chars.len(), &mut Vec::new())
+        },
+    })
+}
+
+/// A problem found by [`lint`]. Only undefined rules stop the rules from
+/// being matched; the rest are warnings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lint {
+    Undefined { rule: RuleNum, missing: RuleNum },
+    Unreachable(RuleNum),
+    /// A cycle of rules that each start with the next, starting and
+    /// ending at the same rule. The regex compiler can't handle these.
+    LeftRecursive(Vec<RuleNum>),
+    /// A message that matches in more than one way.
+    Ambiguous(String),
+}
+
+impl Lint {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Lint::Undefined { .. })
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lint::Undefined { rule, missing } => write!(f, "rule {rule} refers to undefined rule {missing}"),
+            Lint::Unreachable(r) => write!(f, "rule {r} is unreachable"),
+            Lint::LeftRecursive(cycle) => {
+                let cycle: Vec<String> = cycle.iter().map(|r| r.to_string()).collect();
+                write!(f, "rules are left-recursive: {}", cycle.join(" -> "))
+            },
+            Lint::Ambiguous(m) => write!(f, "message {m:?} matches in more than one way"),
+        }
+    }
+}
+
+/// Checks `rules` for undefined references, rules that can't be reached
+/// from `root` and left recursion. Each of `messages` that matches `root`
+/// is also checked for ambiguity.
+pub fn lint(rules: &HashMap<RuleNum, Rule>, root: RuleNum, messages: &[String]) -> Vec<Lint> {
+    let mut out: Vec<Lint> = undefined(rules)
+        .into_iter()
+        .map(|(rule, missing)| Lint::Undefined { rule, missing })
+        .collect();
+
+    let mut seen = HashSet::from([root]);
+    let mut stack = vec![root];
+    while let Some(r) = stack.pop() {
+        for n in rules.get(&r).into_iter().flat_map(refs) {
+            if seen.insert(*n) {
+                stack.push(*n);
+            }
+        }
+    }
+    let mut unreachable: Vec<RuleNum> = rules.keys().filter(|r| !seen.contains(r)).copied().collect();
+    unreachable.sort();
+    out.extend(unreachable.into_iter().map(Lint::Unreachable));
+
+    // each left-recursive cycle is reported once, by a shortest cycle
+    // through its lowest-numbered rule
+    let mut ids: Vec<RuleNum> = rules.keys().copied().collect();
+    ids.sort();
+    let first = |r: &RuleNum| -> Vec<RuleNum> {
+        match rules.get(r) {
+            Some(Rule::AltSeq(v)) => v.iter().filter_map(|seq| seq.first().copied()).collect(),
+            _ => Vec::new(),
+        }
+    };
+    for &r in &ids {
+        let mut parent: HashMap<RuleNum, RuleNum> = HashMap::new();
+        let mut queue = VecDeque::from([r]);
+        while let Some(n) = queue.pop_front() {
+            if first(&n).contains(&r) {
+                let mut cycle = vec![n];
+                while let Some(p) = parent.get(cycle.last().unwrap()) {
+                    cycle.push(*p);
+                }
+                cycle.reverse();
+                cycle.push(r);
+                out.push(Lint::LeftRecursive(cycle));
+                break;
+            }
+            for m in first(&n) {
+                if m > r && !parent.contains_key(&m) {
+                    parent.insert(m, n);
+                    queue.push_back(m);
+                }
+            }
+        }
+    }
+
+    if matches!(rules.get(&root), Some(Rule::AltSeq(_))) && undefined(rules).is_empty() {
+        for m in messages {
+            let chart = Chart::new(rules, root, m);
+            if chart.count(root, 0, chart.chars.len(), &mut HashMap::new(), &mut Vec::new()) > 1 {
+                out.push(Lint::Ambiguous(m.clone()));
+            }
+        }
+    }
+    out
+}
+
+/// Fails on the first error [`lint`] would report, or if there's no rule
+/// `root`, so that matching can look up every rule it meets. Only
+/// undefined rules are errors, so this just looks for those.
+fn check(rules: &HashMap<RuleNum, Rule>, root: RuleNum) -> Result<()> {
+    lookup(rules, root, None)?;
+    match undefined(rules).first() {
+        Some((rule, missing)) => Err(Error::no_solution(Lint::Undefined { rule: *rule, missing: *missing }.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// A rule and everything it refers to compiled to a single regular
+/// expression, which matches in linear time. Only works for rules without
+/// cycles.
+#[derive(Clone, Debug)]
+pub struct Compiled {
+    pattern: String,
+    re: Regex,
+}
+
+impl Compiled {
+    pub fn new(rules: &HashMap<RuleNum, Rule>, root: RuleNum) -> Result<Self> {
+        let pattern = compile_rule(rules, root, &mut HashMap::new(), &mut Vec::new())?;
+        let re = Regex::new(&format!("^(?:{pattern})$"))
+            .map_err(|e| Error::no_solution(format!("rule {root} doesn't compile: {e}")))?;
+        Ok(Compiled { pattern, re })
+    }
+
+    /// The regular expression, without the anchors that make it match
+    /// whole messages.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_match(&self, s: &str) -> bool {
+        self.re.is_match(s)
+    }
+}
+
+/// The pattern for rule `id`. `memo` holds the rules done so far and
+/// `path` the ones being compiled, for spotting cycles.
+fn compile_rule(
+    rules: &HashMap<RuleNum, Rule>, id: RuleNum, memo: &mut HashMap<RuleNum, String>, path: &mut Vec<RuleNum>,
+) -> Result<String> {
+    if let Some(p) = memo.get(&id) {
+        return Ok(p.clone());
+    }
+    let rule = lookup(rules, id, path.last().copied())?;
+    if let Some(i) = path.iter().position(|r| *r == id) {
+        let cycle: Vec<String> = path[i..].iter().chain([&id]).map(|r| r.to_string()).collect();
+        return Err(Error::no_solution(format!("the rules can't be compiled because of the cycle {}", cycle.join(" -> "))));
+    }
+    path.push(id);
+    let p = match rule {
+        Rule::Char(c) => regex::escape(&c.to_string()),
+        Rule::AltSeq(v) => {
+            let alts = v.iter()
+                .map(|seq| seq.iter().map(|r| compile_rule(rules, *r, memo, path)).collect::<Result<String>>())
+                .collect::<Result<Vec<_>>>()?;
+            if alts.len() == 1 { alts[0].clone() } else { format!("(?:{})", alts.join("|")) }
+        },
+    };
+    path.pop();
+    memo.insert(id, p.clone());
+    Ok(p)
+}
+
+/// Every message of up to `max_len` characters that matches `root`,
+/// shortest first and then in order. The languages of all the rules are
+/// built up one length at a time, so recursive rules are fine.
+pub fn generate(rules: &HashMap<RuleNum, Rule>, root: RuleNum, max_len: usize) -> Vec<String> {
+    // the messages of each (rule, length)
+    let mut lang: HashMap<(RuleNum, usize), BTreeSet<String>> = HashMap::new();
+    for len in 1 ..= max_len {
+        // repeated for rules that are just another rule of the same length
+        loop {
+            let mut changed = false;
+            for (id, rule) in rules {
+                let new: BTreeSet<String> = match rule {
+                    Rule::Char(c) if len == 1 => BTreeSet::from([c.to_string()]),
+                    Rule::Char(_) => BTreeSet::new(),
+                    Rule::AltSeq(v) => v.iter().flat_map(|seq| seq_lang(seq, len, &lang)).collect(),
+                };
+                let cur = lang.entry((*id, len)).or_default();
+                if new.len() > cur.len() {
+                    *cur = new;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+    (1 ..= max_len)
+        .flat_map(|len| lang.get(&(root, len)).into_iter().flatten().cloned())
+        .collect()
+}
+
+/// The messages of exactly `len` characters matching `seq` in turn.
+fn seq_lang(seq: &[RuleNum], len: usize, lang: &HashMap<(RuleNum, usize), BTreeSet<String>>) -> Vec<String> {
+    let Some((first, rest)) = seq.split_first() else {
+        return if len == 0 { vec![String::new()] } else { vec![] };
+    };
+    let mut out = Vec::new();
+    for l in 1 ..= len {
+        let Some(heads) = lang.get(&(*first, l)).filter(|h| !h.is_empty()) else { continue };
+        let tails = seq_lang(rest, len - l, lang);
+        out.extend(heads.iter().flat_map(|h| tails.iter().map(move |t| format!("{h}{t}"))));
+    }
+    out
+}
+
+/// A small xorshift generator, so samples can be repeated from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+/// The length of the shortest message each rule matches. Rules that can't
+/// match anything are left out.
+fn min_lens(rules: &HashMap<RuleNum, Rule>) -> HashMap<RuleNum, usize> {
+    let mut out: HashMap<RuleNum, usize> = HashMap::new();
+    loop {
+        let mut changed = false;
+        for (id, rule) in rules {
+            let len = match rule {
+                Rule::Char(_) => Some(1),
+                Rule::AltSeq(v) => v.iter().filter_map(|seq| seq.iter().map(|r| out.get(r)).sum::<Option<usize>>()).min(),
+            };
+            if let Some(len) = len.filter(|len| out.get(id).is_none_or(|l| len < l)) {
+                out.insert(*id, len);
+                changed = true;
+            }
+        }
+        if !changed {
+            return out;
+        }
+    }
+}
+
+/// `count` random messages of up to `max_len` characters that match
+/// `root`, the same ones for the same `seed`. Each is made by picking
+/// alternatives at random from the ones short enough to fit, so they
+/// aren't spread evenly over the language.
+pub fn sample(
+    rules: &HashMap<RuleNum, Rule>, root: RuleNum, max_len: usize, count: usize, seed: u64,
+) -> Result<Vec<String>> {
+    lookup(rules, root, None)?;
+    let min = min_lens(rules);
+    if min.get(&root).is_none_or(|l| *l > max_len) {
+        return Ok(Vec::new());
+    }
+    let mut rng = Rng(seed.wrapping_mul(2) | 1);
+    (0 .. count)
+        .map(|_| {
+            let mut out = Vec::new();
+            derive(rules, root, max_len, &min, &mut rng, &mut out)?;
+            Ok(out.into_iter().collect())
+        })
+        .collect()
+}
+
+/// Appends a random message of at most `budget` characters matching `rule`
+/// to `out`. `budget` is at least the rule's shortest message.
+fn derive(
+    rules: &HashMap<RuleNum, Rule>, rule: RuleNum, budget: usize,
+    min: &HashMap<RuleNum, usize>, rng: &mut Rng, out: &mut Vec<char>,
+) -> Result<()> {
+    match lookup(rules, rule, None)? {
+        Rule::Char(c) => out.push(*c),
+        Rule::AltSeq(v) => {
+            let seq_min = |seq: &[RuleNum]| seq.iter().map(|r| min.get(r)).sum::<Option<usize>>();
+            let fits: Vec<&Vec<RuleNum>> = v.iter().filter(|seq| seq_min(seq).is_some_and(|m| m <= budget)).collect();
+            let seq = fits[rng.below(fits.len())];
+            let mut slack = budget - seq_min(seq).unwrap();
+            for r in seq {
+                let start = out.len();
+                derive(rules, *r, min[r] + slack, min, rng, out)?;
+                slack -= out.len() - start - min[r];
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Messages keyed by their number of 42s and 31s; see [`group_by_split`].
+pub type SplitGroups = BTreeMap<Option<(usize, usize)>, Vec<String>>;
+
+/// Groups messages by how they split into a run of messages matching
+/// rule 42 followed by a run matching rule 31, keyed by the number of
+/// each, or `None` for messages that don't split that way. Where there's
+/// more than one split, the one with the most 42s is used. Part 2 accepts
+/// the messages with at least one 31 and more 42s than 31s.
+pub fn group_by_split(rules: &HashMap<RuleNum, Rule>, messages: &[String]) -> Result<SplitGroups> {
+    let (r42, r31) = (Compiled::new(rules, 42)?, Compiled::new(rules, 31)?);
+    let mut out = SplitGroups::new();
+    for m in messages {
+        out.entry(split(&r42, &r31, m)).or_default().push(m.clone());
+    }
+    Ok(out)
+}
+
+fn split(r42: &Compiled, r31: &Compiled, s: &str) -> Option<(usize, usize)> {
+    let bounds: Vec<usize> = s.char_indices().map(|(i, _)| i).chain([s.len()]).collect();
+    let n = bounds.len();
+    // the most 42s that make up s[..bounds[i]], and 31s that make up s[bounds[i]..]
+    let mut n42: Vec<Option<usize>> = vec![None; n];
+    let mut n31: Vec<Option<usize>> = vec![None; n];
+    n42[0] = Some(0);
+    n31[n - 1] = Some(0);
+    for i in 1 .. n {
+        n42[i] = (0 .. i)
+            .filter_map(|j| n42[j].filter(|_| r42.is_match(&s[bounds[j] .. bounds[i]])).map(|c| c + 1))
+            .max();
+    }
+    for i in (0 .. n - 1).rev() {
+        n31[i] = (i + 1 .. n)
+            .filter_map(|j| n31[j].filter(|_| r31.is_match(&s[bounds[i] .. bounds[j]])).map(|c| c + 1))
+            .max();
+    }
+    (1 .. n - 1)
+        .filter_map(|i| Some((n42[i]?, n31[i]?)))
+        .filter(|(a, b)| *a > 0 && *b > 0)
+        .max()
+}
+
+fn part1(rules: &HashMap<RuleNum, Rule>, strings: &[String]) -> Result<usize> {
+    check(rules, 0)?;
+    let c = Compiled::new(rules, 0)?;
+    Ok(strings.iter().filter(|s| c.is_match(s)).count())
+}
+
+/// The rules with 8 and 11 replaced by the looping versions from part 2.
+pub fn looping_rules(rules: &HashMap<RuleNum, Rule>) -> Result<HashMap<RuleNum, Rule>> {
+    if let Some(r) = [42, 31].iter().find(|r| !rules.contains_key(r)) {
+        return Err(Error::no_solution(format!("the looping rules need rule {r}, which isn't defined")));
+    }
+    let mut rules = rules.clone();
+    rules.insert(8, Rule::AltSeq(vec![vec![42], vec![42, 8]]));
+    rules.insert(11, Rule::AltSeq(vec![vec![42, 31], vec![42, 11, 31]]));
+    Ok(rules)
+}
+
+fn part2(rules: &HashMap<RuleNum, Rule>, strings: &[String]) -> Result<usize> {
+    let rules = looping_rules(rules)?;
+    let mut n = 0;
+    for s in strings {
+        n += matches(&rules, 0, s)? as usize;
+    }
+    Ok(n)
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    const DAY: u8 = 19;
+    type Input = (HashMap<RuleNum, Rule>, Vec<String>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let groups = numbered_groups(input);
+        let rules = parse_rules(groups.first().map_or(&[], |g| g.as_slice()))?;
+        if !rules.contains_key(&0) {
+            return Err(Error::parse(1, "there is no rule 0").at_line(eof_line(input)));
+        }
+        if let Some(g) = groups.get(2) {
+            return Err(Error::parse(1, "unexpected third section").at_line(g[0].0));
+        }
+        let strings = groups.get(1).map_or(vec![], |g| g.iter().map(|(_, s)| s.to_string()).collect());
+        Ok((rules, strings))
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(&input.0, &input.1)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(&input.0, &input.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day19_test() {
+        let (rules, strings) = Day19::parse(include_str!("day19.testinput")).unwrap();
+        assert_eq!(part1(&rules, &strings), Ok(2));
+        assert_eq!(Compiled::new(&rules, 0).unwrap().pattern(), "a(?:(?:aa|bb)(?:ab|ba)|(?:ab|ba)(?:aa|bb))b");
+        assert!(strings.iter().all(|s| Compiled::new(&rules, 0).unwrap().is_match(s) == matches(&rules, 0, s).unwrap()));
+        assert_eq!(parse_message(&rules, 0, "ababbb").unwrap().unwrap().to_string(), "(0 a (1 (3 b a) (2 b b)) b)");
+        assert_eq!(parse_message(&rules, 0, "aaabbb"), Ok(None));
+        let all = generate(&rules, 0, 6);
+        assert_eq!(all.len(), 8);
+        assert!(all.contains(&"ababbb".to_string()) && all.contains(&"abbbab".to_string()));
+        assert!(all.iter().all(|m| matches(&rules, 0, m) == Ok(true)));
+        assert!(generate(&rules, 0, 5).is_empty());
+
+        let (mut rules, strings) = Day19::parse(include_str!("day19.testinput2")).unwrap();
+        assert_eq!(part2(&rules, &strings), Ok(12));
+        let groups = group_by_split(&rules, &strings).unwrap();
+        assert_eq!(groups.iter().filter(|(k, _)| matches!(k, Some((a, b)) if a > b)).map(|(_, v)| v.len()).sum::<usize>(), 12);
+        let looping = looping_rules(&rules).unwrap();
+        let samples = sample(&looping, 0, 40, 20, 2020).unwrap();
+        assert_eq!(samples, sample(&looping, 0, 40, 20, 2020).unwrap());
+        assert!(samples.iter().all(|m| m.len() <= 40 && matches(&looping, 0, m) == Ok(true)));
+        assert!(generate(&looping, 0, 15).iter().all(|m| matches!(split(
+            &Compiled::new(&rules, 42).unwrap(), &Compiled::new(&rules, 31).unwrap(), m), Some((a, b)) if a > b)));
+        rules.insert(8, Rule::AltSeq(vec![vec![42], vec![42, 8]]));
+        let left: HashMap<RuleNum, Rule> = [
+            (0, Rule::AltSeq(vec![vec![0, 1], vec![1]])),
+            (1, Rule::Char('a')),
+        ].into_iter().collect();
+        assert_eq!(parse_message(&left, 0, "aaa").unwrap().unwrap().to_string(), "(0 (0 (0 a) a) a)");
+        assert_eq!(matches(&left, 0, ""), Ok(false));
+
+        let mut bad: HashMap<RuleNum, Rule> = [
+            (0, Rule::AltSeq(vec![vec![1, 2], vec![2, 2]])),
+            (1, Rule::AltSeq(vec![vec![3, 1], vec![2]])),
+            (2, Rule::Char('a')),
+            (3, Rule::AltSeq(vec![vec![1, 4], vec![5]])),
+            (4, Rule::Char('b')),
+            (6, Rule::AltSeq(vec![vec![6], vec![7]])),
+        ].into_iter().collect();
+        assert_eq!(
+            lint(&bad, 0, &["aa".into(), "aba".into(), "ab".into()]),
+            vec![
+                Lint::Undefined { rule: 3, missing: 5 },
+                Lint::Undefined { rule: 6, missing: 7 },
+                Lint::Unreachable(6),
+                Lint::LeftRecursive(vec![1, 3, 1]),
+                Lint::LeftRecursive(vec![6, 6]),
+            ],
+        );
+        assert_eq!(matches(&bad, 0, "aa"), Err(Error::no_solution("rule 3 refers to undefined rule 5")));
+        bad.insert(3, Rule::AltSeq(vec![vec![1, 4]]));
+        bad.remove(&6);
+        assert_eq!(lint(&bad, 0, &["aa".into(), "aba".into(), "aaba".into()]), vec![
+            Lint::LeftRecursive(vec![1, 3, 1]),
+            Lint::Ambiguous("aa".into()),
+        ]);
+        assert_eq!(
+            Compiled::new(&rules, 0).err(),
+            Some(Error::no_solution("the rules can't be compiled because of the cycle 8 -> 8")),
+        );
+        let missing: HashMap<RuleNum, Rule> = [
+            (0, Rule::AltSeq(vec![vec![1, 9], vec![1]])),
+            (1, Rule::Char('a')),
+        ].into_iter().collect();
+        assert_eq!(Compiled::new(&missing, 0).err(), Some(Error::no_solution("rule 0 refers to undefined rule 9")));
+        assert_eq!(sample(&missing, 0, 5, 3, 1), Ok(vec!["a".into(); 3]));
+        assert_eq!(sample(&missing, 2, 5, 3, 1), Err(Error::no_solution("there is no rule 2")));
+        assert_eq!(matches(&missing, 0, "a"), Err(Error::no_solution("rule 0 refers to undefined rule 9")));
+        assert_eq!(parse_message(&missing, 3, "a"), Err(Error::no_solution("there is no rule 3")));
+
+        assert_eq!(
+            Day19::parse("0: 1 2\n1: \"a\"\n2: 1 x\n").err(),
+            Some(Error::Parse { line: 3, column: 6, message: "\"x\": invalid digit found in string".into() }),
+        );
+        assert_eq!(
+            Day19::parse("0: 1 2\n1: \"a\"\n").err(),
+            Some(Error::Parse { line: 1, column: 1, message: "rule 0 refers to undefined rule 2".into() }),
+        );
+    }
+}
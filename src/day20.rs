@@ -0,0 +1,891 @@
+use std::collections::{HashMap,HashSet};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::vec::Vec;
+use ya_advent_lib::grid::Grid;
+use crate::image::{Canvas, Rgb};
+use crate::input::{check_chars, field, numbered_groups};
+use crate::{Error, Result, Solver};
+
+/// A side of a tile read in one direction: top and bottom left to right,
+/// left and right top to bottom, or the other way for the `Rev` ones.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Edge {
+    Top,
+    TopRev,
+    Bot,
+    BotRev,
+    Left,
+    LeftRev,
+    Right,
+    RightRev,
+}
+
+impl Edge {
+    pub const ALL: [Edge; 8] = [
+        Edge::Top, Edge::TopRev, Edge::Bot, Edge::BotRev,
+        Edge::Left, Edge::LeftRev, Edge::Right, Edge::RightRev,
+    ];
+
+    /// The same side read the other way.
+    pub fn rev(&self) -> Edge {
+        match self {
+            Edge::Top => Edge::TopRev,
+            Edge::TopRev => Edge::Top,
+            Edge::Bot => Edge::BotRev,
+            Edge::BotRev => Edge::Bot,
+            Edge::Left => Edge::LeftRev,
+            Edge::LeftRev => Edge::Left,
+            Edge::Right => Edge::RightRev,
+            Edge::RightRev => Edge::Right,
+        }
+    }
+
+    /// The corners the edge runs between, with the tile centred on the
+    /// origin and corners at ±1.
+    fn ends(&self) -> [(i64, i64); 2] {
+        let (a, b) = match self {
+            Edge::Top | Edge::TopRev => ((-1, -1), (1, -1)),
+            Edge::Bot | Edge::BotRev => ((-1, 1), (1, 1)),
+            Edge::Left | Edge::LeftRev => ((-1, -1), (-1, 1)),
+            Edge::Right | Edge::RightRev => ((1, -1), (1, 1)),
+        };
+        match self {
+            Edge::TopRev | Edge::BotRev | Edge::LeftRev | Edge::RightRev => [b, a],
+            _ => [a, b],
+        }
+    }
+}
+
+/// An edge packed into an integer, one bit per cell with the first cell
+/// in the lowest bit, so edges of up to 128 cells can be compared and
+/// hashed as numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeSig {
+    bits: u128,
+    len: u32,
+}
+
+impl EdgeSig {
+    fn new(cells: impl Iterator<Item=bool>) -> Self {
+        cells.fold(EdgeSig { bits: 0, len: 0 }, |s, c| EdgeSig { bits: s.bits | (c as u128) << s.len, len: s.len + 1 })
+    }
+
+    pub fn reversed(&self) -> Self {
+        EdgeSig { bits: self.bits.reverse_bits() >> (128 - self.len), len: self.len }
+    }
+
+    /// The smaller of the signature and its reverse, which is the same
+    /// whichever way the edge is read.
+    pub fn canonical(&self) -> Self {
+        (*self).min(self.reversed())
+    }
+}
+
+/// The sides of every tile, keyed by their canonical signatures.
+pub struct EdgeIndex(HashMap<EdgeSig, Vec<(usize, Edge)>>);
+
+impl EdgeIndex {
+    /// Each side is stored read in the direction that gives its canonical
+    /// signature.
+    pub fn new(tiles: &[Tile]) -> Self {
+        let mut index: HashMap<EdgeSig, Vec<(usize, Edge)>> = HashMap::with_capacity(tiles.len() * 4);
+        for (i, t) in tiles.iter().enumerate() {
+            for side in [Edge::Top, Edge::Bot, Edge::Left, Edge::Right] {
+                let sig = t.edge(side);
+                let e = if sig == sig.canonical() { side } else { side.rev() };
+                index.entry(sig.canonical()).or_default().push((i, e));
+            }
+        }
+        EdgeIndex(index)
+    }
+
+    /// The sides, as (tile index, edge), that have `sig` either way round.
+    pub fn sharing(&self, sig: EdgeSig) -> &[(usize, Edge)] {
+        self.0.get(&sig.canonical()).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Each way a tile's edge reads as `sig`: both directions of a side if
+    /// it reads the same either way.
+    pub fn reading(&self, sig: EdgeSig) -> impl Iterator<Item=(usize, Edge)> + '_ {
+        let (fwd, palindrome) = (sig == sig.canonical(), sig == sig.reversed());
+        self.sharing(sig).iter().flat_map(move |(t, e)| {
+            let e = if fwd { *e } else { e.rev() };
+            [Some((*t, e)), palindrome.then(|| (*t, e.rev()))].into_iter().flatten()
+        })
+    }
+}
+
+/// An element of the dihedral group D4: one of the eight ways to rotate
+/// and flip a tile. Each is a 2x2 matrix acting on coordinates centred on
+/// the tile, built from a quarter turn clockwise and a left-right mirror,
+/// and composition, inverses and how edges move all come from multiplying
+/// those matrices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orient(u8);
+
+type Matrix = [i64; 4];
+
+const fn mul(a: Matrix, b: Matrix) -> Matrix {
+    [
+        a[0] * b[0] + a[1] * b[2], a[0] * b[1] + a[1] * b[3],
+        a[2] * b[0] + a[3] * b[2], a[2] * b[1] + a[3] * b[3],
+    ]
+}
+
+/// The matrix of each orientation: `i % 4` quarter turns clockwise, then
+/// a mirror if `i >= 4`. With y pointing down, a clockwise turn takes
+/// `(x, y)` to `(-y, x)`.
+const MATRICES: [Matrix; 8] = {
+    const TURN: Matrix = [0, -1, 1, 0];
+    const MIRROR: Matrix = [-1, 0, 0, 1];
+    let mut out = [[1, 0, 0, 1]; 8];
+    let mut i = 1;
+    while i < 8 {
+        out[i] = if i == 4 { MIRROR } else { mul(TURN, out[i - 1]) };
+        i += 1;
+    }
+    out
+};
+
+impl Orient {
+    pub const IDENTITY: Orient = Orient(0);
+    pub const ROT90: Orient = Orient(1);
+    pub const FLIP: Orient = Orient(4);
+    pub const ALL: [Orient; 8] = [
+        Orient(0), Orient(1), Orient(2), Orient(3), Orient(4), Orient(5), Orient(6), Orient(7),
+    ];
+
+    fn matrix(&self) -> Matrix {
+        MATRICES[self.0 as usize]
+    }
+
+    fn from_matrix(m: Matrix) -> Orient {
+        Orient::ALL.into_iter().find(|o| o.matrix() == m).unwrap()
+    }
+
+    /// Applying `self` and then `then`.
+    pub fn then(&self, then: Orient) -> Orient {
+        Orient::from_matrix(mul(then.matrix(), self.matrix()))
+    }
+
+    pub fn inverse(&self) -> Orient {
+        let [a, b, c, d] = self.matrix();
+        Orient::from_matrix([a, c, b, d])
+    }
+
+    fn apply(&self, (x, y): (i64, i64)) -> (i64, i64) {
+        let [a, b, c, d] = self.matrix();
+        (a * x + b * y, c * x + d * y)
+    }
+
+    /// Whether the orientation swaps width and height.
+    pub fn transposes(&self) -> bool {
+        self.matrix()[0] == 0
+    }
+
+    /// Where edge `e` ends up.
+    pub fn edge(&self, e: Edge) -> Edge {
+        let ends = e.ends().map(|p| self.apply(p));
+        Edge::ALL.into_iter().find(|e| e.ends() == ends).unwrap()
+    }
+
+    /// The orientation that moves edge `from` onto edge `to`. There's
+    /// always exactly one.
+    pub fn mapping(from: Edge, to: Edge) -> Orient {
+        Orient::ALL.into_iter().find(|o| o.edge(from) == to).unwrap()
+    }
+
+    /// Where `(x, y)` of a `w` by `h` grid ends up.
+    pub fn map(&self, x: i64, y: i64, w: i64, h: i64) -> (i64, i64) {
+        // doubled so the centre is on whole numbers
+        let (cx, cy) = self.apply((2 * x - (w - 1), 2 * y - (h - 1)));
+        let (nw, nh) = if self.transposes() { (h, w) } else { (w, h) };
+        ((cx + nw - 1) / 2, (cy + nh - 1) / 2)
+    }
+
+    /// A copy of `grid` in this orientation, with its corner at (0, 0).
+    pub fn transform<T: Copy>(&self, grid: &Grid<T>) -> Grid<T> {
+        let (xs, ys) = (grid.x_bounds(), grid.y_bounds());
+        let (w, h) = (xs.end - xs.start, ys.end - ys.start);
+        let (nw, nh) = if self.transposes() { (h, w) } else { (w, h) };
+        let mut out = Grid::new(0, 0, nw - 1, nh - 1, grid.get(xs.start, ys.start));
+        grid.for_each(|v, x, y| {
+            let (nx, ny) = self.map(x - xs.start, y - ys.start, w, h);
+            out.set(nx, ny, v);
+        });
+        out
+    }
+}
+
+pub struct Tile {
+    id: u64,
+    grid: Grid<bool>,
+    /// Indexed by `Edge as usize`.
+    edges: [EdgeSig; 8],
+}
+
+impl Tile {
+    fn new(input: &[(usize, &str)]) -> Result<Self> {
+        let (line, header) = input[0];
+        let id = header
+            .strip_prefix("Tile ")
+            .and_then(|s| s.strip_suffix(':'))
+            .ok_or_else(|| Error::parse(1, "expected \"Tile <id>:\"").at_line(line))
+            .and_then(|s| field::<u64>(s, 5).map_err(|e| e.at_line(line)))?;
+        let rows = &input[1..];
+        check_chars(rows.iter().copied(), "#.")?;
+        let width = rows.first().map_or(0, |(_, row)| row.len());
+        if let Some((n, row)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(Error::parse(1, format!(
+                "tile {id}'s first row is {width} wide, so each row should be too, not {}", row.len(),
+            )).at_line(*n));
+        }
+        if rows.len() < 3 || width < 3 {
+            return Err(Error::parse(1, format!("tile {id} is too small")).at_line(line));
+        }
+        if rows.len() > 128 || width > 128 {
+            return Err(Error::parse(1, format!("tile {id} is too large; sides can be at most 128 long")).at_line(line));
+        }
+        let data:Vec<String> = rows.iter().map(|(_, row)| row.to_string()).collect();
+
+        let grid = Grid::from_input_map(&data, false, 0, |c| c == '#');
+
+        let (right_x, bot_y) = (grid.x_bounds().end - 1, grid.y_bounds().end - 1);
+        let top = EdgeSig::new(grid.x_bounds().map(|x| grid.get(x, 0)));
+        let bot = EdgeSig::new(grid.x_bounds().map(|x| grid.get(x, bot_y)));
+        let left = EdgeSig::new(grid.y_bounds().map(|y| grid.get(0, y)));
+        let right = EdgeSig::new(grid.y_bounds().map(|y| grid.get(right_x, y)));
+        let edges = [
+            top, top.reversed(), bot, bot.reversed(),
+            left, left.reversed(), right, right.reversed(),
+        ];
+
+        Ok(Self { id, grid, edges })
+    }
+
+    pub fn edge(&self, e: Edge) -> EdgeSig {
+        self.edges[e as usize]
+    }
+
+    fn width(&self) -> i64 {
+        self.grid.x_bounds().end
+    }
+
+    fn height(&self) -> i64 {
+        self.grid.y_bounds().end
+    }
+
+    fn rotate(&self, orient: Orient) -> Self {
+        let inv = orient.inverse();
+        Self {
+            id: self.id,
+            grid: orient.transform(&self.grid),
+            edges: Edge::ALL.map(|e| self.edge(inv.edge(e))),
+        }
+    }
+}
+
+fn mktiles(input: &str) -> Result<Vec<Tile>> {
+    let groups = numbered_groups(input);
+    let tiles = groups
+        .iter()
+        .map(|g| Tile::new(g))
+        .collect::<Result<Vec<_>>>()?;
+    let mut ids = HashSet::new();
+    for (g, t) in groups.iter().zip(tiles.iter()) {
+        if !ids.insert(t.id) {
+            return Err(Error::parse(6, format!("tile {} appears twice", t.id)).at_line(g[0].0));
+        }
+    }
+    Ok(tiles)
+}
+
+fn part1(tiles: &[Tile]) -> Result<u64> {
+    let a = assemble(tiles);
+    a.check()?;
+    let corners = [(0, 0), (0, a.cols - 1), (a.rows - 1, 0), (a.rows - 1, a.cols - 1)];
+    corners
+        .iter()
+        .try_fold(1u64, |acc, (r, c)| acc.checked_mul(a.placed[r * a.cols + c].id))
+        .ok_or_else(|| Error::no_solution("the product of the corner tile ids is too large"))
+}
+
+/// Where a tile went in the assembled image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub id: u64,
+    pub row: usize,
+    pub col: usize,
+    pub orient: Orient,
+}
+
+/// The result of [`assemble`]. If not every tile could be placed, `placed`
+/// is the furthest the search got, and the rest are in `unplaced`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assembly {
+    pub rows: usize,
+    pub cols: usize,
+    /// The placed tiles in row-major order.
+    pub placed: Vec<Placement>,
+    pub unplaced: Vec<u64>,
+    /// Tiles with an edge that matches more than one other tile, which
+    /// the search had to try each of.
+    pub ambiguous: Vec<u64>,
+}
+
+impl Assembly {
+    pub fn is_complete(&self) -> bool {
+        self.unplaced.is_empty() && !self.placed.is_empty()
+    }
+
+    /// Fails unless every tile was placed.
+    pub fn check(&self) -> Result<()> {
+        match self.unplaced.as_slice() {
+            [] if self.placed.is_empty() => Err(Error::no_solution("there are no tiles")),
+            [] => Ok(()),
+            [id] => Err(Error::no_solution(format!("tile {id} doesn't fit in the image"))),
+            ids => Err(Error::no_solution(format!(
+                "{} tiles don't fit in the image: {}",
+                ids.len(), ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", "),
+            ))),
+        }
+    }
+}
+
+/// The tiles and which of them have each edge, for finding the ones that
+/// fit next to tiles already placed.
+struct Assembler<'a> {
+    tiles: &'a [Tile],
+    index: EdgeIndex,
+    /// How many of each tile's sides match some other tile.
+    matched: Vec<usize>,
+}
+
+impl Assembler<'_> {
+    /// What tile `t` in orientation `o` has along `side`.
+    fn edge(&self, (t, o): (usize, Orient), side: Edge) -> EdgeSig {
+        self.tiles[t].edge(o.inverse().edge(side))
+    }
+
+    /// The orientations of tiles that put `sig` along `side`.
+    fn fitting(&self, sig: EdgeSig, side: Edge) -> Vec<(usize, Orient)> {
+        self.index.reading(sig).map(|(t, e)| (t, Orient::mapping(e, side))).collect()
+    }
+
+    /// Fills a `rows` by `cols` grid in row-major order, backtracking when
+    /// a tile that fits leads to a dead end. `cells` holds the (tile,
+    /// orientation) placed so far, and `best` the longest run of them seen.
+    /// Tiles with as many matching sides as the cell has neighbours are
+    /// tried first, so corners and edges go to the border.
+    fn search(
+        &self, rows: usize, cols: usize, order: &[usize],
+        cells: &mut Vec<(usize, Orient)>, used: &mut [bool], best: &mut Vec<(usize, Orient)>,
+    ) -> bool {
+        if cells.len() > best.len() {
+            *best = cells.clone();
+        }
+        let k = cells.len();
+        if k == rows * cols {
+            return true;
+        }
+        let (r, c) = (k / cols, k % cols);
+        let mut candidates: Vec<(usize, Orient)> = if c > 0 {
+            self.fitting(self.edge(cells[k - 1], Edge::Right), Edge::Left)
+        } else if r > 0 {
+            self.fitting(self.edge(cells[k - cols], Edge::Bot), Edge::Top)
+        } else {
+            order.iter().flat_map(|t| Orient::ALL.map(|o| (*t, o))).collect()
+        };
+        let neighbours = (r > 0) as usize + (r + 1 < rows) as usize + (c > 0) as usize + (c + 1 < cols) as usize;
+        candidates.sort_by_key(|(t, _)| self.matched[*t] != neighbours);
+        for (t, o) in candidates {
+            if used[t] || (r > 0 && self.edge((t, o), Edge::Top) != self.edge(cells[k - cols], Edge::Bot)) {
+                continue;
+            }
+            used[t] = true;
+            cells.push((t, o));
+            if self.search(rows, cols, order, cells, used, best) {
+                return true;
+            }
+            cells.pop();
+            used[t] = false;
+        }
+        false
+    }
+}
+
+/// Assembles the tiles into a rectangle, placing tiles of any size or
+/// shape as long as the edges that meet are the same. Tiles with no edge in
+/// common with any other are left out, and the rest are tried in each
+/// rectangle they could make, squarest first.
+///
+/// The search backtracks, so it is exponential in the worst case: with
+/// many tiles whose edges match several others (thousands of small tiles
+/// with colliding edges, say) it can run for minutes. Puzzle inputs, where
+/// almost every edge matches at most one other tile, take milliseconds.
+pub fn assemble(tiles: &[Tile]) -> Assembly {
+    let index = EdgeIndex::new(tiles);
+    let matching = |i: usize, e: Edge| index.sharing(tiles[i].edge(e)).iter().filter(|(j, _)| *j != i).count();
+    let sides = [Edge::Top, Edge::Bot, Edge::Left, Edge::Right];
+    let ambiguous: Vec<u64> = (0 .. tiles.len())
+        .filter(|i| sides.iter().any(|e| matching(*i, *e) > 1))
+        .map(|i| tiles[i].id)
+        .collect();
+    let mut usable: Vec<usize> = (0 .. tiles.len())
+        .filter(|i| tiles.len() == 1 || sides.iter().any(|e| matching(*i, *e) > 0))
+        .collect();
+    // likely corners first
+    usable.sort_by_key(|i| sides.iter().filter(|e| matching(*i, **e) > 0).count());
+
+    let matched: Vec<usize> = (0 .. tiles.len())
+        .map(|i| sides.iter().filter(|e| matching(i, **e) > 0).count())
+        .collect();
+    let asm = Assembler { tiles, index, matched };
+
+    let n = usable.len();
+    let mut shapes: Vec<(usize, usize)> = (1 ..= n).filter(|r| n.is_multiple_of(*r)).map(|r| (r, n / r)).collect();
+    shapes.sort_by_key(|(r, c)| r.abs_diff(*c));
+    let mut best: Vec<(usize, Orient)> = Vec::new();
+    let mut best_shape = (0, 0);
+    for (rows, cols) in shapes {
+        let mut cells = Vec::with_capacity(n);
+        let mut attempt = Vec::new();
+        let done = asm.search(rows, cols, &usable, &mut cells, &mut vec![false; tiles.len()], &mut attempt);
+        if done || attempt.len() > best.len() {
+            best = attempt;
+            best_shape = (rows, cols);
+        }
+        if done {
+            break;
+        }
+    }
+
+    let (rows, cols) = best_shape;
+    let placed: Vec<Placement> = best
+        .iter()
+        .enumerate()
+        .map(|(k, (t, o))| Placement { id: tiles[*t].id, row: k / cols.max(1), col: k % cols.max(1), orient: *o })
+        .collect();
+    let unplaced: Vec<u64> = tiles
+        .iter()
+        .map(|t| t.id)
+        .filter(|id| !placed.iter().any(|p| p.id == *id))
+        .collect();
+    Assembly { rows, cols, placed, unplaced, ambiguous }
+}
+
+/// Where a tile ended up in the image built by [`layout`], in image cells
+/// with its border trimmed off.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TileRect {
+    pub id: u64,
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+/// The stitched image together with the tiles it was stitched from.
+#[derive(Clone)]
+pub struct Layout {
+    pub grid: Grid<bool>,
+    pub tiles: Vec<TileRect>,
+}
+
+/// The image made by assembling the tiles and trimming their borders.
+pub fn arrange(tiles: &[Tile]) -> Result<Grid<bool>> {
+    layout(tiles).map(|l| l.grid)
+}
+
+/// Like [`arrange`], but also says where each tile went.
+pub fn layout(tiles: &[Tile]) -> Result<Layout> {
+    let a = assemble(tiles);
+    a.check()?;
+    let by_id: HashMap<u64, &Tile> = tiles.iter().map(|t| (t.id, t)).collect();
+    let oriented: Vec<Tile> = a.placed.iter().map(|p| by_id[&p.id].rotate(p.orient)).collect();
+    // edges that meet are the same length, so every tile in a column is as
+    // wide as the one at the top, and every tile in a row as high as the
+    // one at the left
+    let mut xs = vec![0];
+    for c in 0 .. a.cols {
+        xs.push(xs[c] + oriented[c].width() - 2);
+    }
+    let mut ys = vec![0];
+    for r in 0 .. a.rows {
+        ys.push(ys[r] + oriented[r * a.cols].height() - 2);
+    }
+    let mut grid = Grid::new(0, 0, xs[a.cols] - 1, ys[a.rows] - 1, false);
+    let mut rects = Vec::with_capacity(a.placed.len());
+    for (p, t) in a.placed.iter().zip(oriented.iter()) {
+        let (xoff, yoff) = (xs[p.col], ys[p.row]);
+        t.grid.for_each(|val, x, y| {
+            if x > 0 && y > 0 && x < t.width() - 1 && y < t.height() - 1 {
+                grid.set(xoff + x - 1, yoff + y - 1, val);
+            }
+        });
+        rects.push(TileRect { id: p.id, x: xoff, y: yoff, width: t.width() - 2, height: t.height() - 2 });
+    }
+    Ok(Layout { grid, tiles: rects })
+}
+
+const WATER: Rgb = [0x0b, 0x2a, 0x4a];
+const WAVE: Rgb = [0x4a, 0x90, 0xc8];
+const MONSTER: Rgb = [0xf0, 0x80, 0x20];
+const BORDER: Rgb = [0x70, 0x70, 0x70];
+const LABEL: Rgb = [0xff, 0xf0, 0x80];
+// pixels per image cell in exported files
+const EXPORT_SCALE: usize = 4;
+
+fn hex(c: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", c[0], c[1], c[2])
+}
+
+impl Layout {
+    /// The image drawn `scale` pixels per cell, with the cells of `matches`
+    /// coloured, an outline around each tile and its id in the corner.
+    pub fn canvas(&self, matches: &[Match], scale: usize) -> Canvas {
+        let (xs, ys) = (self.grid.x_bounds(), self.grid.y_bounds());
+        let mut c = Canvas::new(xs.end as usize * scale, ys.end as usize * scale, WATER);
+        let covered: HashSet<(i64, i64)> = matches.iter().flat_map(|m| m.cells.iter().copied()).collect();
+        self.grid.for_each(|val, x, y| {
+            let color = if covered.contains(&(x, y)) { MONSTER } else if val { WAVE } else { return };
+            c.fill(x as usize * scale, y as usize * scale, scale, scale, color);
+        });
+        for t in &self.tiles {
+            let (x, y) = (t.x as usize * scale, t.y as usize * scale);
+            c.outline(x, y, t.width as usize * scale, t.height as usize * scale, BORDER);
+            c.text(x + 2, y + 2, 1, &t.id.to_string(), LABEL);
+        }
+        c
+    }
+
+    /// The same picture as [`Layout::canvas`], as an SVG document.
+    pub fn svg(&self, matches: &[Match], scale: usize) -> String {
+        let (w, h) = (self.grid.x_bounds().end, self.grid.y_bounds().end);
+        let covered: HashSet<(i64, i64)> = matches.iter().flat_map(|m| m.cells.iter().copied()).collect();
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {w} {h}\">\n",
+            w as usize * scale, h as usize * scale,
+        );
+        out += &format!("<rect width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n", hex(WATER));
+        for (val, x, y) in self.grid.iter_with_coord() {
+            let color = if covered.contains(&(x, y)) { MONSTER } else if val { WAVE } else { continue };
+            out += &format!("<rect x=\"{x}\" y=\"{y}\" width=\"1\" height=\"1\" fill=\"{}\"/>\n", hex(color));
+        }
+        for t in &self.tiles {
+            out += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.1\"/>\n",
+                t.x, t.y, t.width, t.height, hex(BORDER),
+            );
+            out += &format!(
+                "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"1.5\" fill=\"{}\">{}</text>\n",
+                t.x as f64 + 0.3, t.y as f64 + 1.6, hex(LABEL), t.id,
+            );
+        }
+        out += "</svg>\n";
+        out
+    }
+}
+
+/// Writes the assembled image, with the sea monsters coloured, to `path` as
+/// a PPM, PNG or SVG file depending on its extension.
+pub fn export(tiles: &[Tile], path: &Path) -> Result<()> {
+    let layout = layout(tiles)?;
+    let matches = SEA_MONSTER.parse::<Pattern>()?.find(&layout.grid, Overlap::Allow);
+    let data = match path.extension().and_then(|e| e.to_str()) {
+        Some("ppm") => layout.canvas(&matches, EXPORT_SCALE).to_ppm(),
+        Some("png") => layout.canvas(&matches, EXPORT_SCALE).to_png(),
+        Some("svg") => layout.svg(&matches, EXPORT_SCALE).into_bytes(),
+        _ => { return Err(Error::Io(format!("{}: expected a .ppm, .png or .svg file name", path.display()))); },
+    };
+    fs::write(path, data).map_err(|e| Error::io(path, e))
+}
+
+/// The sea monster from the puzzle, as a template for [`Pattern`].
+pub const SEA_MONSTER: &str = concat!(
+    "                  # \n",
+    "#    ##    ##    ###\n",
+    " #  #  #  #  #  #   \n",
+);
+
+/// A shape to look for in an image, read from an ASCII drawing where `#`
+/// marks the cells that have to be set and spaces or `.` the ones that
+/// don't matter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    coords: Vec<(i64, i64)>,
+    width: i64,
+    height: i64,
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<(usize, &str)> = s.lines().enumerate().map(|(i, l)| (i + 1, l.trim_end_matches('\r'))).collect();
+        check_chars(lines.iter().copied(), "#. ")?;
+        let coords: Vec<(i64, i64)> = lines
+            .iter()
+            .flat_map(|(n, l)| l.char_indices().filter(|(_, c)| *c == '#').map(move |(x, _)| (x as i64, *n as i64 - 1)))
+            .collect();
+        if coords.is_empty() {
+            return Err(Error::parse(1, "the pattern has no \"#\" in it").at_line(1));
+        }
+        Ok(Pattern {
+            width: coords.iter().map(|(x, _)| x + 1).max().unwrap(),
+            height: coords.iter().map(|(_, y)| y + 1).max().unwrap(),
+            coords,
+        })
+    }
+}
+
+/// Where a pattern was found, and the cells it covers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub x: i64,
+    pub y: i64,
+    pub orient: Orient,
+    pub cells: Vec<(i64, i64)>,
+}
+
+/// What [`Pattern::find`] does with matches that share cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overlap {
+    /// Keep them all.
+    Allow,
+    /// Keep a match only if it doesn't share a cell with an earlier one.
+    Skip,
+}
+
+impl Pattern {
+    /// Reads a pattern drawing from the file at `path`.
+    pub fn load(path: &Path) -> Result<Pattern> {
+        fs::read_to_string(path).map_err(|e| Error::io(path, e))?.parse()
+    }
+
+    pub fn orient(&self, o: Orient) -> Pattern {
+        let mut coords: Vec<(i64, i64)> = self.coords.iter().map(|(x, y)| o.map(*x, *y, self.width, self.height)).collect();
+        coords.sort();
+        let (width, height) = if o.transposes() { (self.height, self.width) } else { (self.width, self.height) };
+        Pattern { coords, width, height }
+    }
+
+    /// Every place the pattern appears in `grid` in any orientation, in
+    /// reading order. Orientations that look the same as an earlier one
+    /// aren't searched again.
+    pub fn find(&self, grid: &Grid<bool>, overlap: Overlap) -> Vec<Match> {
+        let mut shapes: Vec<(Orient, Pattern)> = Vec::new();
+        for o in Orient::ALL {
+            let p = self.orient(o);
+            if !shapes.iter().any(|(_, q)| *q == p) {
+                shapes.push((o, p));
+            }
+        }
+        let (xs, ys) = (grid.x_bounds(), grid.y_bounds());
+        let mut out: Vec<Match> = Vec::new();
+        let mut covered: HashSet<(i64, i64)> = HashSet::new();
+        for y in ys.clone() {
+            for x in xs.clone() {
+                for (o, p) in &shapes {
+                    if x + p.width > xs.end || y + p.height > ys.end
+                        || !p.coords.iter().all(|(cx, cy)| grid.get(x + cx, y + cy)) {
+                        continue;
+                    }
+                    let cells: Vec<(i64, i64)> = p.coords.iter().map(|(cx, cy)| (x + cx, y + cy)).collect();
+                    if overlap == Overlap::Skip && cells.iter().any(|c| covered.contains(c)) {
+                        continue;
+                    }
+                    covered.extend(cells.iter().copied());
+                    out.push(Match { x, y, orient: *o, cells });
+                }
+            }
+        }
+        out
+    }
+}
+
+/// The image as text, with the cells of `matches` drawn as `O`, other set
+/// cells as `#` and the rest as `.`.
+pub fn render(grid: &Grid<bool>, matches: &[Match]) -> String {
+    let covered: HashSet<(i64, i64)> = matches.iter().flat_map(|m| m.cells.iter().copied()).collect();
+    let mut out = String::new();
+    for y in grid.y_bounds() {
+        for x in grid.x_bounds() {
+            out.push(if covered.contains(&(x, y)) { 'O' } else if grid.get(x, y) { '#' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn part2(tiles: &[Tile]) -> Result<usize> {
+    let grid = arrange(tiles)?;
+    let matches = SEA_MONSTER.parse::<Pattern>()?.find(&grid, Overlap::Allow);
+    if matches.is_empty() {
+        return Err(Error::no_solution("there are no sea monsters in the image"));
+    }
+    let covered: HashSet<(i64, i64)> = matches.iter().flat_map(|m| m.cells.iter().copied()).collect();
+    Ok(grid.iter_with_coord()
+        .filter(|(v, x, y)| *v && !covered.contains(&(*x, *y)))
+        .count())
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    const DAY: u8 = 20;
+    type Input = Vec<Tile>;
+    type Part1 = u64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        mktiles(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        part1(input)
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day20_test() {
+        let tiles = mktiles(include_str!("day20.testinput")).unwrap();
+        assert_eq!(part1(&tiles), Ok(20899048083289));
+        let lone = Day20::parse("Tile 99999999999:\n#.#\n...\n#.#\n").unwrap();
+        assert_eq!(part1(&lone), Err(Error::no_solution("the product of the corner tile ids is too large")));
+        assert_eq!(part2(&tiles), Ok(273));
+        let grid = arrange(&tiles).unwrap();
+        let monster: Pattern = SEA_MONSTER.parse().unwrap();
+        let found = monster.find(&grid, Overlap::Allow);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|m| m.orient == found[0].orient && m.cells.len() == 15));
+        let image = render(&grid, &found);
+        assert_eq!(image.matches('O').count(), 30);
+        assert_eq!(image.matches('#').count(), 273);
+
+        let l = layout(&tiles).unwrap();
+        assert_eq!(l.tiles.len(), 9);
+        assert!(l.tiles.iter().all(|t| t.width == 8 && t.height == 8 && t.x % 8 == 0 && t.y % 8 == 0));
+        let c = l.canvas(&found, 4);
+        assert_eq!((c.width(), c.height()), (96, 96));
+        let (mx, my) = found[0].cells[0];
+        assert_eq!(c.get(mx as usize * 4 + 1, my as usize * 4 + 1), MONSTER);
+        assert_eq!(c.get(0, 50), BORDER);
+        let svg = l.svg(&found, 4);
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<text ").count(), 9);
+        assert_eq!(svg.matches(&hex(MONSTER)).count(), 30);
+        assert!(svg.contains(">1951</text>"));
+        let file = std::env::temp_dir().join(format!("day20-export-{}.png", std::process::id()));
+        assert_eq!(export(&tiles, &file), Ok(()));
+        assert_eq!(fs::read(&file).unwrap(), l.canvas(&found, EXPORT_SCALE).to_png());
+        fs::remove_file(&file).unwrap();
+        assert!(matches!(export(&tiles, &file.with_extension("gif")), Err(Error::Io(_))));
+
+        // a 2 by 2 block overlaps itself in a 3 by 2 block, and a bar
+        // looks the same rotated by 180 degrees
+        let g = Grid::from_input_map(&["###".to_string(), "###".to_string()], false, 0, |c| c == '#');
+        let block: Pattern = "##\n##\n".parse().unwrap();
+        assert_eq!(block.find(&g, Overlap::Allow).len(), 2);
+        assert_eq!(block.find(&g, Overlap::Skip).iter().map(|m| (m.x, m.y)).collect::<Vec<_>>(), vec![(0, 0)]);
+        let bar: Pattern = "#.#\n".parse().unwrap();
+        assert_eq!(bar.find(&g, Overlap::Allow).len(), 2);
+        assert_eq!(render(&g, &bar.find(&g, Overlap::Skip)), "O#O\nO#O\n");
+        let file = std::env::temp_dir().join(format!("day20-pattern-{}", std::process::id()));
+        fs::write(&file, ".#.\n#.#\n\n x\n").unwrap();
+        assert_eq!(
+            Pattern::load(&file),
+            Err(Error::Parse { line: 4, column: 2, message: "unexpected character 'x'".into() }),
+        );
+        fs::write(&file, SEA_MONSTER).unwrap();
+        assert_eq!(Pattern::load(&file), Ok(monster));
+        fs::remove_file(&file).unwrap();
+        assert!(matches!(Pattern::load(&file), Err(Error::Io(m)) if m.starts_with(&file.display().to_string())));
+        assert_eq!(
+            "# x\n".parse::<Pattern>(),
+            Err(Error::Parse { line: 1, column: 3, message: "unexpected character 'x'".into() }),
+        );
+
+        for a in Orient::ALL {
+            assert_eq!(a.then(a.inverse()), Orient::IDENTITY);
+            for e in Edge::ALL {
+                assert_eq!(Orient::mapping(e, a.edge(e)), a);
+                assert_eq!(a.then(Orient::FLIP).edge(e), Orient::FLIP.edge(a.edge(e)));
+            }
+        }
+        assert_eq!(Orient::ROT90.edge(Edge::Top), Edge::Right);
+        assert_eq!(Orient::ROT90.edge(Edge::Left), Edge::TopRev);
+        assert_eq!(Orient::FLIP.then(Orient::ROT90), Orient::ROT90.inverse().then(Orient::FLIP));
+        assert_eq!(Orient::ROT90.map(0, 0, 20, 3), (2, 0));
+        let g = Grid::from_input_map(&["#..".to_string(), "##.".to_string()], false, 0, |c| c == '#');
+        assert_eq!(Orient::ROT90.transform(&g).format_str(|c| if c { "#".into() } else { ".".into() }), "##\n#.\n..\n");
+
+        let sig = tiles[0].edge(Edge::Top);
+        assert_eq!((sig.reversed(), sig.reversed().reversed()), (tiles[0].edge(Edge::TopRev), sig));
+        assert_eq!(sig.canonical(), sig.reversed().canonical());
+        let index = EdgeIndex::new(&tiles);
+        assert!(tiles.iter().enumerate().all(|(i, t)| index
+            .sharing(t.edge(Edge::Left))
+            .iter()
+            .any(|(j, e)| *j == i && matches!(e, Edge::Left | Edge::LeftRev))));
+        let pal = EdgeSig::new([true, false, true].into_iter());
+        assert_eq!(pal, pal.canonical());
+
+        let a = assemble(&tiles);
+        assert!(a.is_complete() && a.ambiguous.is_empty());
+        assert_eq!((a.rows, a.cols, a.placed.len()), (3, 3, 9));
+
+        // 2 by 3 tiles of 6 by 4, cut from a pseudo-random picture so
+        // that neighbours share their edges
+        let (w, h, rows, cols) = (6, 4, 2, 3);
+        let mut seed = 7u32;
+        let pic: Vec<Vec<char>> = (0 .. rows * (h - 1) + 1).map(|_| (0 .. cols * (w - 1) + 1).map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            if seed >> 16 & 1 == 1 { '#' } else { '.' }
+        }).collect()).collect();
+        let mut text = String::new();
+        for (r, c) in [(1, 2), (0, 0), (1, 0), (0, 2), (0, 1), (1, 1)] {
+            text += &format!("Tile {}:\n", 10 * r + c + 1);
+            for row in &pic[r * (h - 1) .. r * (h - 1) + h] {
+                text += &row[c * (w - 1) .. c * (w - 1) + w].iter().collect::<String>();
+                text += "\n";
+            }
+            text += "\n";
+        }
+        let tiles = mktiles(&text).unwrap();
+        let a = assemble(&tiles);
+        assert!(a.is_complete());
+        assert_eq!(a.rows * a.cols, 6);
+        let grid = arrange(&tiles).unwrap();
+        let dims = (grid.x_bounds().end, grid.y_bounds().end);
+        assert!(dims == (3 * 4, 2 * 2) || dims == (2 * 2, 3 * 4));
+
+        let tiles = mktiles(&(text.clone() + "Tile 99:\n######\n#....#\n.####.\n")).unwrap();
+        let a = assemble(&tiles);
+        assert_eq!((a.placed.len(), a.unplaced.clone()), (6, vec![99]));
+        assert_eq!(arrange(&tiles).err(), Some(Error::no_solution("tile 99 doesn't fit in the image")));
+        let tiles = mktiles("Tile 1:\n#..\n...\n...\n\nTile 2:\n.##\n###\n##.\n").unwrap();
+        assert_eq!(part1(&tiles), Err(Error::no_solution("2 tiles don't fit in the image: 1, 2")));
+        assert_eq!(assemble(&[]).check(), Err(Error::no_solution("there are no tiles")));
+        let dup = text.split("\n\n").next().unwrap().replacen("Tile 13:", "Tile 98:", 1);
+        let tiles = mktiles(&(text + &dup)).unwrap();
+        let amb = assemble(&tiles).ambiguous;
+        assert!(amb.contains(&13) && amb.contains(&98));
+
+        assert_eq!(
+            mktiles("Tile 7:\n#...\n.#.\n..\n").err(),
+            Some(Error::Parse { line: 3, column: 1, message: "tile 7's first row is 4 wide, so each row should be too, not 3".into() }),
+        );
+        assert_eq!(
+            mktiles("Tile x:\n#..\n.#.\n...\n").err(),
+            Some(Error::Parse { line: 1, column: 6, message: "\"x\": invalid digit found in string".into() }),
+        );
+    }
+}
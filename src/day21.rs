@@ -0,0 +1,117 @@
+use std::collections::{HashMap,HashSet};
+use std::str::FromStr;
+use std::vec::Vec;
+use itertools::Itertools;
+use crate::input::read_lines;
+use crate::{Error, Result, Solver};
+
+pub struct Food {
+    ingredients: HashSet<String>,
+    allergens: HashSet<String>,
+}
+impl FromStr for Food {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((ing, alg)) = s.split_once(" (contains ") else {
+            return Err(Error::parse(s.len() + 1, "expected \" (contains \""));
+        };
+        let Some(alg2) = alg.strip_suffix(')') else {
+            return Err(Error::parse(s.len() + 1, "expected \")\""));
+        };
+        if ing.is_empty() {
+            return Err(Error::parse(1, "expected a list of ingredients"));
+        }
+        let ingredients = ing.split(' ').map(|s| s.into()).collect();
+        let allergens = alg2.split(", ").map(|s| s.into()).collect();
+        Ok(Self{ ingredients, allergens })
+    }
+}
+
+fn candidates(input: &[Food]) -> HashMap<String, HashSet<String>> {
+    let mut allergen_map:HashMap<String, HashSet<String>> = HashMap::new();
+    for food in input {
+        for al in &food.allergens {
+            allergen_map.entry(al.clone())
+                .and_modify(|set| *set = set.intersection(&food.ingredients).cloned().collect())
+                .or_insert(food.ingredients.clone());
+        }
+    }
+    allergen_map
+}
+
+fn count_safe(input: &[Food]) -> usize {
+    let all_allergens:HashSet<String> = candidates(input)
+        .into_values().flatten().collect();
+    input.iter()
+        .flat_map(|food| food.ingredients.iter())
+        .filter(|i| !all_allergens.contains(*i))
+        .count()
+}
+
+fn dangerous(input: &[Food]) -> Result<String> {
+    let mut allergen_map = candidates(input);
+    let mut matched: HashMap<String,String> = HashMap::new();
+    while !allergen_map.is_empty() {
+        let Some((alg, ing)) = allergen_map
+            .iter()
+            .filter(|(_,v)| v.len() == 1)
+            .map(|(k,v)| (k, v.iter().next().unwrap().clone()))
+            .next()
+        else {
+            return Err(Error::no_solution(format!(
+                "can't tell which ingredients contain {}",
+                allergen_map.keys().sorted().join(", "),
+            )));
+        };
+        let alg = alg.clone();
+        allergen_map.remove(&alg);
+        allergen_map.iter_mut().for_each(|(_,v)| { v.remove(&ing); });
+        matched.insert(ing, alg);
+    }
+    Ok(matched.iter()
+        .sorted_by(|a,b| Ord::cmp(a.1, b.1))
+        .map(|(k,_)| k)
+        .join(","))
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    const DAY: u8 = 21;
+    type Input = Vec<Food>;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_lines(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(count_safe(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(dangerous(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day21_test() {
+        let input:Vec<Food> = read_lines(include_str!("day21.testinput")).unwrap();
+        assert_eq!(count_safe(&input), 5);
+        assert_eq!(dangerous(&input).unwrap(), "mxmxvkd,sqjhc,fvjkl");
+
+        assert_eq!(
+            read_lines::<Food>("a b (contains x)\nc d (contains y\n").err(),
+            Some(Error::Parse { line: 2, column: 16, message: "expected \")\"".into() }),
+        );
+        let input:Vec<Food> = read_lines("a b (contains x, y)\n").unwrap();
+        assert!(matches!(dangerous(&input), Err(Error::NoSolution(_))));
+        // part 1 doesn't need the allergens to be resolvable
+        let input:Vec<Food> = read_lines("a b (contains x, y)\nc d (contains z)\n").unwrap();
+        assert_eq!(Day21::part1(&input), Ok(0));
+        assert!(matches!(Day21::part2(&input), Some(Err(Error::NoSolution(_)))));
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::{HashSet, VecDeque};
+use std::vec::Vec;
+use crate::input::{eof_line, field, numbered_groups};
+use crate::{Error, Result, Solver};
+
+enum Winner {
+    P1,
+    P2,
+}
+
+fn recursive_game(mut p1deck: VecDeque<usize>, mut p2deck: VecDeque<usize>) -> (Winner, VecDeque<usize>) {
+    let mut states: HashSet<(VecDeque<usize>,VecDeque<usize>)> = HashSet::new();
+
+    loop {
+        let card1 = p1deck.pop_front().unwrap();
+        let card2 = p2deck.pop_front().unwrap();
+
+        let winner = if card1 <= p1deck.len() && card2 <= p2deck.len() {
+            let newp1deck = p1deck.iter().take(card1).cloned().collect();
+            let newp2deck = p2deck.iter().take(card2).cloned().collect();
+            let result = recursive_game(newp1deck, newp2deck);
+            result.0
+        }
+        else if card1 > card2 {
+            Winner::P1
+        }
+        else {
+            Winner::P2
+        };
+
+        match winner {
+            Winner::P1 => {
+                p1deck.push_back(card1);
+                p1deck.push_back(card2);
+            },
+            Winner::P2 => {
+                p2deck.push_back(card2);
+                p2deck.push_back(card1);
+            },
+        }
+
+        if p1deck.is_empty() {
+            return (Winner::P2, p2deck);
+        }
+        if p2deck.is_empty() {
+            return (Winner::P1, p1deck);
+        }
+
+        let state = (p1deck.clone(), p2deck.clone());
+        if states.contains(&state) {
+            return (Winner::P1, p1deck);
+        }
+        states.insert(state);
+    }
+}
+
+type Decks = (VecDeque<usize>, VecDeque<usize>);
+
+fn parse_deck(player: usize, group: Option<&Vec<(usize, &str)>>, eof: usize) -> Result<VecDeque<usize>> {
+    let header = format!("Player {player}:");
+    let Some(group) = group else {
+        return Err(Error::parse(1, format!("expected \"{header}\"")).at_line(eof));
+    };
+    if group[0].1 != header {
+        return Err(Error::parse(1, format!("expected \"{header}\"")).at_line(group[0].0));
+    }
+    if group.len() == 1 {
+        return Err(Error::parse(1, format!("player {player} has no cards")).at_line(group[0].0));
+    }
+    group[1..].iter()
+        .map(|(n, l)| field::<usize>(l, 0).map_err(|e| e.at_line(*n)))
+        .collect()
+}
+
+fn parse_decks(input: &str) -> Result<Decks> {
+    let groups = numbered_groups(input);
+    let eof = eof_line(input);
+    if let Some(g) = groups.get(2) {
+        return Err(Error::parse(1, "unexpected third section").at_line(g[0].0));
+    }
+    Ok((parse_deck(1, groups.first(), eof)?, parse_deck(2, groups.get(1), eof)?))
+}
+
+fn part1(input: &Decks) -> usize {
+    let mut p1deck = input.0.clone();
+    let mut p2deck = input.1.clone();
+    let winner;
+    loop {
+        let card1 = p1deck.pop_front().unwrap();
+        let card2 = p2deck.pop_front().unwrap();
+        if card1 > card2 {
+            p1deck.push_back(card1);
+            p1deck.push_back(card2);
+        } else {
+            p2deck.push_back(card2);
+            p2deck.push_back(card1);
+        }
+        if p1deck.is_empty() {
+            winner = p2deck;
+            break;
+        }
+        if p2deck.is_empty() {
+            winner = p1deck;
+            break;
+        }
+    }
+    winner.iter().rev().enumerate()
+        .map(|(idx, card)| card * (idx+1))
+        .sum()
+}
+
+fn part2(input: &Decks) -> usize {
+    let (_, deck) = recursive_game(input.0.clone(), input.1.clone());
+    deck.iter().rev().enumerate()
+        .map(|(idx, card)| card * (idx+1))
+        .sum()
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    const DAY: u8 = 22;
+    type Input = Decks;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_decks(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(Ok(part2(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day22_test() {
+        let input = parse_decks(include_str!("day22.testinput")).unwrap();
+        assert_eq!(part1(&input), 306);
+        assert_eq!(part2(&input), 291);
+
+        assert_eq!(
+            parse_decks("Player 1:\n9\n2\n").err(),
+            Some(Error::Parse { line: 4, column: 1, message: "expected \"Player 2:\"".into() }),
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::ops::Range;
+use std::vec::Vec;
+use itertools::Itertools;
+use crate::input::numbered_lines;
+use crate::{Error, Result, Solver};
+
+#[allow(dead_code)]
+fn dbg_print(cups: &[usize]) {
+    let mut c: usize = 1;
+    while cups[c-1] != 1 {
+        print!("{} ", ((c as u8) + b'0') as char);
+        c = cups[c-1];
+    }
+    println!();
+}
+
+fn do_move(current: usize, cups: &mut [usize]) -> usize {
+    let taken1 = cups[current-1];
+    let taken2 = cups[taken1-1];
+    let taken3 = cups[taken2-1];
+    let next = cups[taken3-1];
+    let mut dest = current - 1;
+    loop {
+        if dest == 0 { dest = cups.len(); }
+        if dest != taken1 && dest != taken2 && dest != taken3 {
+            break;
+        }
+        dest -= 1;
+    }
+    let afterdest = cups[dest-1];
+    cups[dest-1] = taken1;
+    cups[taken3-1] = afterdest;
+    cups[current-1] = next;
+    next
+}
+
+fn populate_initial(seq: &[usize], cups: &mut Vec<usize>, extra: Range<usize>) -> usize{
+    let start = seq[0];
+    let mut last = 0;
+    assert_eq!(cups.len(), seq.len());
+    seq
+        .iter()
+        .circular_tuple_windows()
+        .for_each(|(&n, &nxt)| {
+            last = n;
+            cups[n-1] = nxt;
+        });
+    if !extra.is_empty() {
+        assert_eq!(extra.start, seq.len()+1);
+        cups[last-1] = extra.start;
+        for v in extra.clone() { cups.push(v+1); }
+        cups[extra.end-2] = start;
+    }
+    start
+}
+
+fn part1(input: &str) -> String {
+    let mut cups:Vec<usize> = vec![0; input.chars().count()];
+    let initial_seq = input.chars()
+        .map(|c| ((c as u8) - b'0') as usize)
+        .collect::<Vec<_>>();
+    let mut current = populate_initial(&initial_seq, &mut cups, 0..0);
+    for _i in 0..100 {
+        current = do_move(current, &mut cups);
+        // if _i < 10 {
+        //     dbg_print(&cups);
+        // }
+    }
+    let mut out = String::new();
+    let mut c = cups[0];
+    while c != 1 {
+        out += &String::from((c as u8 + b'0') as char);
+        c = cups[c-1];
+    }
+    out
+}
+
+fn part2(input: &str) -> usize {
+    let mut cups:Vec<usize> = Vec::with_capacity(1_000_000);
+    cups.resize(input.len(), 0);
+
+    let initial_seq = input.chars()
+        .map(|c| ((c as u8) - b'0') as usize)
+        .collect::<Vec<_>>();
+    let extra = initial_seq.len() + 1 .. 1_000_001;
+    let mut current = populate_initial(&initial_seq, &mut cups, extra);
+    for _ in 0..10_000_000 {
+        current = do_move(current, &mut cups);
+    }
+    cups[0] * cups[cups[0]-1]
+}
+
+/// The cup labels must be the digits 1 to n in some order, with at least
+/// five cups so a move always has a destination.
+fn parse_cups(input: &str) -> Result<String> {
+    let mut lines = numbered_lines(input);
+    let Some((n, line)) = lines.next() else {
+        return Err(Error::parse(1, "expected a list of cup labels").at_line(1));
+    };
+    if let Some((n, _)) = lines.next() {
+        return Err(Error::parse(1, "expected a single line of cup labels").at_line(n));
+    }
+    let len = line.chars().count();
+    if !(5..=9).contains(&len) {
+        return Err(Error::parse(1, format!("expected 5 to 9 cups, found {len}")).at_line(n));
+    }
+    let mut seen = [false; 10];
+    for (col, c) in line.char_indices() {
+        match c.to_digit(10) {
+            Some(d) if d >= 1 && d as usize <= len && !seen[d as usize] => { seen[d as usize] = true; },
+            _ => {
+                return Err(Error::parse(col + 1, format!(
+                    "expected the labels 1 to {len} each used once, found {c:?}"
+                )).at_line(n));
+            },
+        }
+    }
+    Ok(line.into())
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    const DAY: u8 = 23;
+    type Input = String;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_cups(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>> {
+        Some(Ok(part2(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day23_test() {
+        let input:String = "389125467".into();
+        assert_eq!(part1(&input), "67384529");
+        assert_eq!(part2(&input), 149245887792);
+        assert_eq!(
+            parse_cups("3891254677\n").err(),
+            Some(Error::Parse { line: 1, column: 1, message: "expected 5 to 9 cups, found 10".into() }),
+        );
+        assert_eq!(
+            parse_cups("38912546\n").err(),
+            Some(Error::Parse { line: 1, column: 3, message: "expected the labels 1 to 8 each used once, found '9'".into() }),
+        );
+    }
+}
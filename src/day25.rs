@@ -0,0 +1,97 @@
+use std::vec::Vec;
+use crate::input::{eof_line, field, numbered_lines};
+use crate::{Error, Result, Solver};
+
+fn modpow(base: u64, exp: u64, m: u64) -> u64 {
+    if exp == 0 { return 1; }
+    let mut res = 1;
+    let mut base = base % m;
+    let mut exp = exp;
+    loop {
+        if exp % 2 == 1 {
+            res *= base;
+            res %= m;
+        }
+        if exp == 1 {
+            return res;
+        }
+        base *= base;
+        base %= m;
+        exp /= 2;
+    }
+}
+
+/// The card's and the door's public keys, which must be valid keys in the
+/// 20201227 handshake.
+fn parse_keys(input: &str) -> Result<Vec<u64>> {
+    let mut keys = Vec::with_capacity(2);
+    for (n, line) in numbered_lines(input) {
+        if keys.len() == 2 {
+            return Err(Error::parse(1, "expected only two public keys").at_line(n));
+        }
+        let key = field::<u64>(line, 0).map_err(|e| e.at_line(n))?;
+        if key == 0 || key >= 20201227 {
+            return Err(Error::parse(1, format!("{key} is not a public key modulo 20201227")).at_line(n));
+        }
+        keys.push(key);
+    }
+    if keys.len() < 2 {
+        return Err(Error::parse(1, "expected two public keys").at_line(eof_line(input)));
+    }
+    Ok(keys)
+}
+
+fn part1(input: &[u64]) -> u64 {
+    let mut card_loop = 1u64;
+    while modpow(7, card_loop, 20201227) != input[0] {
+        card_loop += 1;
+    }
+    let card_key = modpow(input[1], card_loop, 20201227);
+
+    #[cfg(debug_assertions)]
+    {
+        let mut door_loop = 1u64;
+        while modpow(7, door_loop, 20201227) != input[1] {
+            door_loop += 1;
+        }
+        let door_key = modpow(input[0], door_loop, 20201227);
+        assert_eq!(card_key, door_key);
+    }
+
+    card_key
+}
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    const DAY: u8 = 25;
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    // there is no part 2 on the last day
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_keys(input)
+    }
+    fn part1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(part1(input))
+    }
+    fn part2(_input: &Self::Input) -> Option<Result<Self::Part2>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day25_test() {
+        let input:Vec<u64> = vec![5764801, 17807724];
+        assert_eq!(part1(&input), 14897079);
+        assert_eq!(
+            parse_keys("5764801\n20201227\n").err(),
+            Some(Error::Parse { line: 2, column: 1, message: "20201227 is not a public key modulo 20201227".into() }),
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::path::Path;
+
+/// Errors returned by the solvers' parse and solve paths.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Malformed input. `line` and `column` are 1-based; `line` is 0 until
+    /// the error has been tagged by one of the line-parsing helpers in
+    /// [`crate::input`].
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed but the puzzle has no answer for it.
+    NoSolution(String),
+    /// Reading or writing a file failed. The message starts with the path.
+    Io(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub fn parse(column: usize, message: impl Into<String>) -> Self {
+        Error::Parse { line: 0, column, message: message.into() }
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Error::NoSolution(message.into())
+    }
+
+    pub fn io(path: &Path, e: io::Error) -> Self {
+        Error::Io(format!("{}: {e}", path.display()))
+    }
+
+    /// Sets the line number of a `Parse` error that doesn't have one yet.
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Error::Parse { line: 0, column, message } => Error::Parse { line, column, message },
+            e => e,
+        }
+    }
+
+    /// Shifts the column of a `Parse` error, for errors from a parser that
+    /// was handed a substring starting at (0-based) byte `offset`.
+    pub fn at_offset(self, offset: usize) -> Self {
+        match self {
+            Error::Parse { line, column, message } => Error::Parse { line, column: column + offset, message },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse { line: 0, column, message } =>
+                write!(f, "column {column}: {message}"),
+            Error::Parse { line, column, message } =>
+                write!(f, "line {line}, column {column}: {message}"),
+            Error::NoSolution(message) => write!(f, "no solution: {message}"),
+            Error::Io(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::parse(1, e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        let message = e.to_string();
+        // serde_json appends " at line L column C" to its messages
+        let message = match message.rfind(" at line ") {
+            Some(i) => message[..i].to_string(),
+            None => message,
+        };
+        Error::Parse { line: e.line(), column: e.column(), message }
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
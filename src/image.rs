@@ -0,0 +1,185 @@
+/// An RGB colour.
+pub type Rgb = [u8; 3];
+
+/// A fixed-size RGB raster that can be written out as PPM or PNG, for
+/// looking at puzzle state that's too big to read as text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+// 3x5 pixel digits, one bit per pixel, top row in the high bits
+const DIGITS: [u16; 10] = [
+    0b111_101_101_101_111,
+    0b010_110_010_010_111,
+    0b111_001_111_100_111,
+    0b111_001_111_001_111,
+    0b101_101_111_001_001,
+    0b111_100_111_001_111,
+    0b111_100_111_101_111,
+    0b111_001_001_001_001,
+    0b111_101_111_101_111,
+    0b111_101_111_001_111,
+];
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Canvas { width, height, pixels: vec![background; width * height] }
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Sets a pixel; pixels outside the canvas are ignored.
+    pub fn set(&mut self, x: usize, y: usize, c: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = c;
+        }
+    }
+
+    pub fn fill(&mut self, x: usize, y: usize, w: usize, h: usize, c: Rgb) {
+        for py in y .. (y + h).min(self.height) {
+            for px in x .. (x + w).min(self.width) {
+                self.set(px, py, c);
+            }
+        }
+    }
+
+    /// A one pixel wide outline just inside the given rectangle.
+    pub fn outline(&mut self, x: usize, y: usize, w: usize, h: usize, c: Rgb) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        self.fill(x, y, w, 1, c);
+        self.fill(x, y + h - 1, w, 1, c);
+        self.fill(x, y, 1, h, c);
+        self.fill(x + w - 1, y, 1, h, c);
+    }
+
+    /// Draws the digits of `s` with their top left corner at `x`, `y`, each
+    /// digit pixel being a `scale` by `scale` square. Characters other than
+    /// digits are left as gaps.
+    pub fn text(&mut self, x: usize, y: usize, scale: usize, s: &str, c: Rgb) {
+        for (i, ch) in s.chars().enumerate() {
+            let Some(d) = ch.to_digit(10) else { continue };
+            let bits = DIGITS[d as usize];
+            for row in 0 .. 5 {
+                for col in 0 .. 3 {
+                    if bits >> (14 - row * 3 - col) & 1 == 1 {
+                        self.fill(x + (i * 4 + col) * scale, y + row * scale, scale, scale, c);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The image as a binary (P6) PPM file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flatten());
+        out
+    }
+
+    /// The image as a PNG file. The pixel data is stored uncompressed,
+    /// which is valid PNG and keeps the encoder short.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0); // filter type: none
+            raw.extend(row.iter().flatten());
+        }
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(0xffff).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend([1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(block) = blocks.next() {
+            let len = block.len() as u16;
+            zlib.push(blocks.peek().is_none() as u8);
+            zlib.extend(len.to_le_bytes());
+            zlib.extend((!len).to_le_bytes());
+            zlib.extend(block);
+        }
+        zlib.extend(adler32(&raw).to_be_bytes());
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend((self.width as u32).to_be_bytes());
+        ihdr.extend((self.height as u32).to_be_bytes());
+        ihdr.extend([8, 2, 0, 0, 0]); // 8 bits per sample, RGB
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut out, b"IHDR", &ihdr);
+        png_chunk(&mut out, b"IDAT", &zlib);
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0 .. 8 {
+            crc = if crc & 1 == 1 { crc >> 1 ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &x in data {
+        a = (a + x as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_test() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let mut c = Canvas::new(2, 1, [0, 0, 0]);
+        c.set(1, 0, [1, 2, 3]);
+        c.set(2, 0, [9, 9, 9]);
+        assert_eq!(c.to_ppm(), b"P6\n2 1\n255\n\0\0\0\x01\x02\x03");
+
+        let png = c.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        // IHDR's crc covers the chunk type and data
+        assert_eq!(&png[29..33], crc32(&png[12..29]).to_be_bytes());
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+        // zlib header, one final stored block of 7 bytes, then the pixels
+        assert_eq!(&png[41..55], b"\x78\x01\x01\x07\x00\xf8\xff\x00\0\0\0\x01\x02\x03");
+
+        let mut c = Canvas::new(8, 5, [0, 0, 0]);
+        c.text(0, 0, 1, "17", [1, 1, 1]);
+        let lit = |c: &Canvas| (0 .. 5)
+            .map(|y| (0 .. 8).map(|x| if c.get(x, y) == [1, 1, 1] { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(lit(&c), vec![".#..###.", "##....#.", ".#....#.", ".#....#.", "###...#."]);
+        c.outline(0, 0, 8, 5, [1, 1, 1]);
+        assert_eq!(lit(&c)[2], "##....##");
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use serde_json::Value;
+use crate::error::{Error, Result};
+
+/// The non-blank lines of `input`, paired with their 1-based line numbers.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item=(usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+/// Splits `input` into groups of numbered lines separated by blank lines.
+pub fn numbered_groups(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            if !group.is_empty() {
+                groups.push(group);
+                group = Vec::new();
+            }
+        }
+        else {
+            group.push((idx + 1, line));
+        }
+    }
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    groups
+}
+
+/// Parses each numbered line with `FromStr`, tagging any error with the
+/// line it came from.
+pub fn parse_lines<'a, T, I>(lines: I) -> Result<Vec<T>>
+where I: IntoIterator<Item=(usize, &'a str)>, T: FromStr, Error: From<T::Err> {
+    lines
+        .into_iter()
+        .map(|(n, line)| line.parse::<T>().map_err(|e| Error::from(e).at_line(n)))
+        .collect()
+}
+
+/// Parses every non-blank line of `input`.
+pub fn read_lines<T>(input: &str) -> Result<Vec<T>>
+where T: FromStr, Error: From<T::Err> {
+    parse_lines(numbered_lines(input))
+}
+
+/// Parses every line of `input`, keeping blank-line separated groups apart.
+pub fn read_groups<T>(input: &str) -> Result<Vec<Vec<T>>>
+where T: FromStr, Error: From<T::Err> {
+    numbered_groups(input)
+        .into_iter()
+        .map(parse_lines)
+        .collect()
+}
+
+/// Checks that every character of the numbered lines is one of `allowed`.
+pub fn check_chars<'a, I>(lines: I, allowed: &str) -> Result<()>
+where I: IntoIterator<Item=(usize, &'a str)> {
+    for (n, line) in lines {
+        if let Some((col, c)) = line.char_indices().find(|(_, c)| !allowed.contains(*c)) {
+            return Err(Error::parse(col + 1, format!("unexpected character {c:?}")).at_line(n));
+        }
+    }
+    Ok(())
+}
+
+/// Parses `s`, a field which starts at (0-based) byte `offset` of its line.
+pub fn field<T>(s: &str, offset: usize) -> Result<T>
+where T: FromStr, T::Err: Display {
+    s.parse::<T>().map_err(|e| Error::parse(offset + 1, format!("{s:?}: {e}")))
+}
+
+/// The 1-based line number just past the end of `input`, for reporting
+/// missing sections.
+pub fn eof_line(input: &str) -> usize {
+    input.lines().count() + 1
+}
+
+/// A `Parse` error at byte `offset` of `s`.
+pub fn error_at(s: &str, offset: usize, message: String) -> Error {
+    let line = s[..offset].matches('\n').count() + 1;
+    let column = offset - s[..offset].rfind('\n').map_or(0, |i| i + 1) + 1;
+    Error::Parse { line, column, message }
+}
+
+/// Byte offset of the first `"key"` at or after `from` in the JSON text `s`,
+/// for pointing errors found after parsing at the entry they're about.
+/// Falls back to `from` if the key was written with escapes.
+pub fn find_key(s: &str, from: usize, key: &str) -> usize {
+    s[from..].find(&format!("{key:?}")).map_or(from, |i| from + i)
+}
+
+/// Walks JSON text of the form `{"day": {"key": value, ...}, ...}`, as used
+/// by the answer and timing files, checking the days and calling
+/// `entry(day, key, value)` for each inner entry. `what` names the inner
+/// keys in error messages; an `Err` message from `entry` is reported at the
+/// position of its key.
+pub fn read_day_map<F>(s: &str, what: &str, mut entry: F) -> Result<()>
+where F: FnMut(u8, &str, Value) -> Result<(), String> {
+    let Value::Object(days) = serde_json::from_str::<Value>(s)? else {
+        return Err(error_at(s, 0, "expected an object of days".into()));
+    };
+    for (day, inner) in days {
+        let day_at = find_key(s, 0, &day);
+        let d = match day.parse::<u8>() {
+            Ok(d) if (1..=25).contains(&d) => d,
+            _ => { return Err(error_at(s, day_at, format!("invalid day: {day:?}"))); },
+        };
+        let Value::Object(inner) = inner else {
+            return Err(error_at(s, day_at, format!("day {d}: expected an object of {what}")));
+        };
+        for (key, value) in inner {
+            let key_at = find_key(s, day_at + day.len() + 2, &key);
+            entry(d, &key, value).map_err(|message| error_at(s, key_at, message))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_test() {
+        let input = "1\n2\n\n3\nx\n";
+        assert_eq!(numbered_groups(input), vec![vec![(1, "1"), (2, "2")], vec![(4, "3"), (5, "x")]]);
+        assert_eq!(read_lines::<u32>("1\n\n2\n"), Ok(vec![1, 2]));
+        assert_eq!(
+            read_groups::<u32>(input),
+            Err(Error::Parse { line: 5, column: 1, message: "invalid digit found in string".into() }),
+        );
+        assert_eq!(
+            field::<u8>("300", 4),
+            Err(Error::parse(5, "\"300\": number too large to fit in target type")),
+        );
+        assert_eq!(
+            check_chars(numbered_lines("#.\n.x"), ".#"),
+            Err(Error::Parse { line: 2, column: 2, message: "unexpected character 'x'".into() }),
+        );
+        let mut seen = Vec::new();
+        let json = "{\"3\": {\"a\": 1, \"b\": 2}}";
+        assert_eq!(read_day_map(json, "letters", |d, k, v| { seen.push((d, k.to_string(), v)); Ok(()) }), Ok(()));
+        assert_eq!(seen, vec![(3, "a".into(), Value::from(1)), (3, "b".into(), Value::from(2))]);
+        assert_eq!(
+            read_day_map(json, "letters", |_, k, _| if k == "b" { Err("no b".into()) } else { Ok(()) }),
+            Err(Error::Parse { line: 1, column: 16, message: "no b".into() }),
+        );
+        assert_eq!(
+            read_day_map("{\"26\": {}}", "letters", |_, _, _| Ok(())),
+            Err(Error::Parse { line: 1, column: 2, message: "invalid day: \"26\"".into() }),
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+use ya_advent_lib::read::input_as_string;
+
+pub mod bench;
+pub mod error;
+pub mod image;
+pub mod input;
+pub mod verify;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub use error::{Error, Result};
+
+/// Common interface implemented by every day's puzzle solution.
+///
+/// `parse` turns the raw puzzle input into the day's input type, which is
+/// then handed to `part1` and `part2`. Days without a second part (day 25)
+/// return `None` from `part2`. Malformed input and puzzles without an answer
+/// are reported through [`Error`].
+pub trait Solver {
+    const DAY: u8;
+    type Input;
+    type Part1: AnswerValue;
+    type Part2: AnswerValue;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1>;
+    fn part2(input: &Self::Input) -> Option<Result<Self::Part2>>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerKind {
+    Integer,
+    String,
+}
+
+impl AnswerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnswerKind::Integer => "integer",
+            AnswerKind::String => "string",
+        }
+    }
+}
+
+/// Implemented by the types a `Solver` can return as an answer.
+pub trait AnswerValue: Display {
+    const KIND: AnswerKind;
+}
+
+impl AnswerValue for i32 { const KIND: AnswerKind = AnswerKind::Integer; }
+impl AnswerValue for i64 { const KIND: AnswerKind = AnswerKind::Integer; }
+impl AnswerValue for isize { const KIND: AnswerKind = AnswerKind::Integer; }
+impl AnswerValue for u64 { const KIND: AnswerKind = AnswerKind::Integer; }
+impl AnswerValue for u128 { const KIND: AnswerKind = AnswerKind::Integer; }
+impl AnswerValue for usize { const KIND: AnswerKind = AnswerKind::Integer; }
+impl AnswerValue for String { const KIND: AnswerKind = AnswerKind::String; }
+
+/// Entry point shared by the `dayNN` binaries: reads the input the same way
+/// `read_input` does and prints both answers, exiting with an error status
+/// if either fails.
+pub fn run<S: Solver>() {
+    let result = S::parse(&input_as_string()).and_then(|input| {
+        println!("Part 1: {}", S::part1(&input)?);
+        if let Some(p2) = S::part2(&input) {
+            println!("Part 2: {}", p2?);
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// One answer produced by [`solve`]. `parse_time` is the time spent in
+/// `Solver::parse`, which is shared by both parts of a day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub value: String,
+    pub kind: AnswerKind,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Parses `input` and runs the requested part (or both parts if `part` is
+/// `None`). Parts that a day doesn't have are left out of the result.
+pub fn solve<S: Solver>(input: &str, part: Option<u8>) -> Result<Vec<Answer>> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let mut out = Vec::with_capacity(2);
+    if part.is_none() || part == Some(1) {
+        let start = Instant::now();
+        let value = S::part1(&input)?;
+        out.push(Answer {
+            day: S::DAY,
+            part: 1,
+            value: value.to_string(),
+            kind: S::Part1::KIND,
+            parse_time,
+            solve_time: start.elapsed(),
+        });
+    }
+    if part.is_none() || part == Some(2) {
+        let start = Instant::now();
+        if let Some(value) = S::part2(&input) {
+            let value = value?;
+            out.push(Answer {
+                day: S::DAY,
+                part: 2,
+                value: value.to_string(),
+                kind: S::Part2::KIND,
+                parse_time,
+                solve_time: start.elapsed(),
+            });
+        }
+    }
+    Ok(out)
+}
+
+pub type SolveFn = fn(&str, Option<u8>) -> Result<Vec<Answer>>;
+
+/// `solve` for every day, indexed by day number - 1.
+pub const SOLVERS: [SolveFn; 25] = [
+    solve::<day01::Day01>,
+    solve::<day02::Day02>,
+    solve::<day03::Day03>,
+    solve::<day04::Day04>,
+    solve::<day05::Day05>,
+    solve::<day06::Day06>,
+    solve::<day07::Day07>,
+    solve::<day08::Day08>,
+    solve::<day09::Day09>,
+    solve::<day10::Day10>,
+    solve::<day11::Day11>,
+    solve::<day12::Day12>,
+    solve::<day13::Day13>,
+    solve::<day14::Day14>,
+    solve::<day15::Day15>,
+    solve::<day16::Day16>,
+    solve::<day17::Day17>,
+    solve::<day18::Day18>,
+    solve::<day19::Day19>,
+    solve::<day20::Day20>,
+    solve::<day21::Day21>,
+    solve::<day22::Day22>,
+    solve::<day23::Day23>,
+    solve::<day24::Day24>,
+    solve::<day25::Day25>,
+];
+
+pub fn solver(day: u8) -> Option<SolveFn> {
+    SOLVERS.get((day as usize).checked_sub(1)?).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_test() {
+        let summary = |answers: Vec<Answer>| answers
+            .into_iter()
+            .map(|a| (a.day, a.part, a.value, a.kind))
+            .collect::<Vec<_>>();
+        let answers = solver(21).unwrap()(include_str!("day21.testinput"), None).unwrap();
+        assert_eq!(summary(answers), vec![
+            (21, 1, "5".into(), AnswerKind::Integer),
+            (21, 2, "mxmxvkd,sqjhc,fvjkl".into(), AnswerKind::String),
+        ]);
+        let answers = solver(23).unwrap()("389125467", Some(1)).unwrap();
+        assert_eq!(summary(answers), vec![(23, 1, "67384529".into(), AnswerKind::String)]);
+        let answers = solver(25).unwrap()("5764801\n17807724\n", None).unwrap();
+        assert_eq!(summary(answers), vec![(25, 1, "14897079".into(), AnswerKind::Integer)]);
+        assert!(solver(25).unwrap()("5764801\n17807724\n", Some(2)).unwrap().is_empty());
+        assert_eq!(
+            solver(1).unwrap()("1721\n979\nfoo\n", None).unwrap_err(),
+            Error::Parse { line: 3, column: 1, message: "invalid digit found in string".into() },
+        );
+        assert!(solver(0).is_none());
+        assert!(solver(26).is_none());
+    }
+}
@@ -0,0 +1,390 @@
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process::exit;
+use serde_json::json;
+use advent2020::{solver, Answer, Solver};
+use advent2020::day20::{self, Day20};
+use advent2020::bench::{bench, change, Baseline, Measurement};
+use advent2020::verify::{verify, Check, Expected};
+
+const USAGE: &str = "\
+usage: advent2020 run [--day N] [--part 1|2] [--input PATH] [--input-dir DIR]
+                      [--format text|json] [--export FILE]
+       advent2020 verify [--day N] [--input PATH] [--input-dir DIR]
+                         [--answers FILE] [--format text|json]
+       advent2020 bench [--day N] [--input PATH] [--input-dir DIR]
+                        [--iterations N] [--baseline FILE] [--save FILE]
+                        [--format text|json]
+
+  --day N          run only day N (default: all 25 days)
+  --part P         run only part P (default: both parts)
+  --input PATH     read the input from PATH, or from stdin if PATH is '-'
+                   (only valid together with --day)
+  --input-dir DIR  directory holding dayNN.input files (default: .)
+  --format FMT     'text' (default) or 'json', which prints one JSON record
+                   per answer with its type and parse/solve times
+
+  --export FILE    (run only, with --day 20) also draw the assembled image,
+                   with the tiles outlined and labelled and the sea monsters
+                   coloured, to FILE as a .ppm, .png or .svg file
+
+  --answers FILE   (verify only) JSON file of expected answers, of the form
+                   {\"1\": {\"1\": 1010299, \"2\": 42140160}, ...}
+                   (default: DIR/answers.json)
+  --iterations N   (bench only) times to run each part (default: 10)
+  --baseline FILE  (bench only) compare the median times with those saved
+                   in FILE
+  --save FILE      (bench only) save the median times to FILE, keeping the
+                   entries already there for days that weren't run
+
+With --day and neither --input nor --input-dir, the input is read from
+stdin if it isn't a terminal, otherwise from ./dayNN.input.
+
+'verify' runs every day and part listed in the answers file (or just --day)
+and reports whether each answer matches, with the difference if it doesn't.
+
+'bench' times the parse, part 1 and part 2 phases of each day separately and
+reports the median, minimum and mean of each.";
+
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(PartialEq)]
+enum Command {
+    Run,
+    Verify,
+    Bench,
+}
+
+struct RunArgs {
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    /// `None` unless `--input-dir` was given; see [`input_dir`].
+    input_dir: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    save: Option<PathBuf>,
+    export: Option<PathBuf>,
+    format: Format,
+}
+
+fn parse_args(cmd: Command, args: &[String]) -> Result<RunArgs, String> {
+    let mut out = RunArgs {
+        day: None,
+        part: None,
+        input: None,
+        input_dir: None,
+        answers: None,
+        iterations: 10,
+        baseline: None,
+        save: None,
+        export: None,
+        format: Format::Text,
+    };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("{arg} requires a value"));
+        match arg.as_str() {
+            "--day" => {
+                let v = value()?;
+                out.day = match v.parse::<u8>() {
+                    Ok(d) if (1..=25).contains(&d) => Some(d),
+                    _ => { return Err(format!("invalid day: {v}")); },
+                };
+            },
+            "--part" if cmd == Command::Run => {
+                let v = value()?;
+                out.part = match v.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => { return Err(format!("invalid part: {v}")); },
+                };
+            },
+            "--input" => { out.input = Some(value()?.clone()); },
+            "--input-dir" => { out.input_dir = Some(PathBuf::from(value()?)); },
+            "--answers" if cmd == Command::Verify => { out.answers = Some(PathBuf::from(value()?)); },
+            "--iterations" if cmd == Command::Bench => {
+                let v = value()?;
+                out.iterations = match v.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => { return Err(format!("invalid iteration count: {v}")); },
+                };
+            },
+            "--baseline" if cmd == Command::Bench => { out.baseline = Some(PathBuf::from(value()?)); },
+            "--save" if cmd == Command::Bench => { out.save = Some(PathBuf::from(value()?)); },
+            "--export" if cmd == Command::Run => { out.export = Some(PathBuf::from(value()?)); },
+            "--format" => {
+                let v = value()?;
+                out.format = match v.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => { return Err(format!("invalid format: {v}")); },
+                };
+            },
+            _ => { return Err(format!("unknown argument: {arg}")); },
+        }
+    }
+    if out.input.is_some() && out.day.is_none() {
+        return Err("--input requires --day".into());
+    }
+    if out.export.is_some() && out.day != Some(20) {
+        return Err("--export requires --day 20".into());
+    }
+    Ok(out)
+}
+
+fn input_dir(args: &RunArgs) -> PathBuf {
+    args.input_dir.clone().unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn read_input(args: &RunArgs, day: u8) -> Result<String, String> {
+    let path = match &args.input {
+        Some(p) if p == "-" => None,
+        Some(p) => Some(PathBuf::from(p)),
+        None if args.day.is_some() && args.input_dir.is_none() && !io::stdin().is_terminal() => None,
+        None => Some(input_dir(args).join(format!("day{day:02}.input"))),
+    };
+    match path {
+        Some(path) => fs::read_to_string(&path)
+            .map_err(|e| format!("{}: {e}", path.display())),
+        None => io::read_to_string(io::stdin())
+            .map_err(|e| format!("stdin: {e}")),
+    }
+}
+
+fn print_answer(a: &Answer, format: &Format) {
+    match format {
+        Format::Text => println!("Day {} Part {}: {}", a.day, a.part, a.value),
+        Format::Json => println!("{}", json!({
+            "day": a.day,
+            "part": a.part,
+            "answer": a.value,
+            "type": a.kind.as_str(),
+            "parse_ns": a.parse_time.as_nanos() as u64,
+            "solve_ns": a.solve_time.as_nanos() as u64,
+        })),
+    }
+}
+
+fn run(args: &RunArgs) -> bool {
+    let days = match args.day {
+        Some(d) => d ..= d,
+        None => 1 ..= 25,
+    };
+    let mut ok = true;
+    for day in days {
+        let input = match read_input(args, day) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                ok = false;
+                continue;
+            },
+        };
+        // drawn before solving, since a picture helps most when it fails
+        if let (20, Some(path)) = (day, &args.export) {
+            if let Err(e) = Day20::parse(&input).and_then(|tiles| day20::export(&tiles, path)) {
+                eprintln!("Day {day}: {e}");
+                ok = false;
+            }
+        }
+        let answers = match solver(day).unwrap()(&input, args.part) {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                ok = false;
+                continue;
+            },
+        };
+        if answers.is_empty() {
+            eprintln!("Day {day} has no part {}", args.part.unwrap_or(2));
+            ok = false;
+        }
+        for a in answers {
+            print_answer(&a, &args.format);
+        }
+    }
+    ok
+}
+
+fn print_check(c: &Check, format: &Format) {
+    match format {
+        Format::Text => match c.diff() {
+            None => println!("Day {} Part {}: ok", c.day, c.part),
+            Some(diff) => println!("Day {} Part {}: FAILED\n  {}", c.day, c.part, diff.replace('\n', "\n  ")),
+        },
+        Format::Json => println!("{}", json!({
+            "day": c.day,
+            "part": c.part,
+            "expected": c.expected,
+            "answer": c.actual.as_ref().ok(),
+            "error": c.actual.as_ref().err().map(|e| e.to_string()),
+            "pass": c.passed(),
+        })),
+    }
+}
+
+fn run_verify(args: &RunArgs) -> bool {
+    let path = args.answers.clone().unwrap_or_else(|| input_dir(args).join("answers.json"));
+    let expected = match fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| s.parse::<Expected>().map_err(|e| e.to_string()))
+    {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            return false;
+        },
+    };
+    let days = match args.day {
+        Some(d) => vec![d],
+        None => expected.days(),
+    };
+    let (mut passed, mut failed) = (0, 0);
+    for day in days {
+        let input = match read_input(args, day) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                failed += 1;
+                continue;
+            },
+        };
+        let checks = verify(&expected, day, &input);
+        if checks.is_empty() {
+            eprintln!("Day {day}: no expected answers in {}", path.display());
+        }
+        for c in checks {
+            if c.passed() { passed += 1; } else { failed += 1; }
+            print_check(&c, &args.format);
+        }
+    }
+    if let Format::Text = args.format {
+        println!("{passed} passed, {failed} failed");
+    }
+    failed == 0
+}
+
+fn read_baseline(path: &PathBuf) -> Result<Baseline, String> {
+    fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| s.parse::<Baseline>().map_err(|e| e.to_string()))
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn print_measurement(m: &Measurement, baseline: Option<&Baseline>, format: &Format) {
+    let base = baseline.and_then(|b| b.get(m.day, m.phase));
+    match format {
+        Format::Text => {
+            print!("Day {:2} {:5}  median {:>10.2?}  min {:>10.2?}  mean {:>10.2?}",
+                m.day, m.phase.as_str(), m.median(), m.min(), m.mean());
+            match base {
+                Some(b) => println!("  baseline {:>10.2?} ({:+.1}%)", b, change(b, m.median())),
+                None => println!(),
+            }
+        },
+        Format::Json => println!("{}", json!({
+            "day": m.day,
+            "phase": m.phase.as_str(),
+            "samples": m.samples.len(),
+            "median_ns": m.median().as_nanos() as u64,
+            "min_ns": m.min().as_nanos() as u64,
+            "mean_ns": m.mean().as_nanos() as u64,
+            "baseline_ns": base.map(|b| b.as_nanos() as u64),
+        })),
+    }
+}
+
+fn run_bench(args: &RunArgs) -> bool {
+    let baseline = match args.baseline.as_ref().map(read_baseline).transpose() {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        },
+    };
+    let days = match args.day {
+        Some(d) => d ..= d,
+        None => 1 ..= 25,
+    };
+    let mut ok = true;
+    let mut all = Vec::new();
+    for day in days {
+        let measurements = read_input(args, day)
+            .and_then(|input| bench(day, &input, args.iterations).map_err(|e| e.to_string()));
+        match measurements {
+            Ok(m) => {
+                for m in &m {
+                    print_measurement(m, baseline.as_ref(), &args.format);
+                }
+                all.extend(m);
+            },
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                ok = false;
+            },
+        }
+    }
+    if let Some(path) = &args.save {
+        let mut saved = if path.exists() {
+            match read_baseline(path) {
+                Ok(b) => b,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return false;
+                },
+            }
+        } else {
+            Baseline::default()
+        };
+        saved.update(&all);
+        if let Err(e) = fs::write(path, saved.to_json() + "\n") {
+            eprintln!("{}: {e}", path.display());
+            ok = false;
+        }
+    }
+    ok
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let ok = match args.first().map(|s| s.as_str()) {
+        Some("run") => match parse_args(Command::Run, &args[1..]) {
+            Ok(a) => run(&a),
+            Err(e) => {
+                eprintln!("{e}\n\n{USAGE}");
+                false
+            },
+        },
+        Some("verify") => match parse_args(Command::Verify, &args[1..]) {
+            Ok(a) => run_verify(&a),
+            Err(e) => {
+                eprintln!("{e}\n\n{USAGE}");
+                false
+            },
+        },
+        Some("bench") => match parse_args(Command::Bench, &args[1..]) {
+            Ok(a) => run_bench(&a),
+            Err(e) => {
+                eprintln!("{e}\n\n{USAGE}");
+                false
+            },
+        },
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{USAGE}");
+            true
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            false
+        },
+    };
+    if !ok {
+        exit(1);
+    }
+}
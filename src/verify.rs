@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use serde_json::Value;
+use crate::input::read_day_map;
+use crate::{solver, Error, Result};
+
+/// Expected answers for the real puzzle inputs, read from a JSON file of the
+/// form
+///
+/// ```json
+/// { "1": { "1": 1010299, "2": 42140160 }, "21": { "2": "mxmxvkd,sqjhc" } }
+/// ```
+///
+/// where the outer keys are days and the inner keys are parts. Answers may
+/// be given as numbers or strings; days and parts can be left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expected(BTreeMap<(u8, u8), String>);
+
+impl Expected {
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self.0.keys().map(|(d, _)| *d).collect();
+        days.dedup();
+        days
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(|s| s.as_str())
+    }
+
+    fn parts(&self, day: u8) -> impl Iterator<Item=(u8, &str)> {
+        self.0.range((day, 0) ..= (day, u8::MAX)).map(|((_, p), a)| (*p, a.as_str()))
+    }
+}
+
+impl FromStr for Expected {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut out = BTreeMap::new();
+        read_day_map(s, "parts", |d, part, answer| {
+            let p = match part {
+                "1" => 1,
+                "2" => 2,
+                _ => { return Err(format!("day {d}: invalid part: {part:?}")); },
+            };
+            let answer = match answer {
+                Value::String(s) => s,
+                Value::Number(n) => n.to_string(),
+                _ => { return Err(format!("day {d} part {p}: expected a number or a string")); },
+            };
+            out.insert((d, p), answer);
+            Ok(())
+        })?;
+        Ok(Expected(out))
+    }
+}
+
+/// The result of checking one part of a day against its expected answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Result<String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.actual.as_deref() == Ok(self.expected.as_str())
+    }
+
+    /// A description of how the answer differs from the expected one, with
+    /// a caret under the first differing character, or `None` if it passed.
+    pub fn diff(&self) -> Option<String> {
+        match &self.actual {
+            Ok(a) if *a == self.expected => None,
+            Ok(a) => {
+                let col = self.expected.chars().zip(a.chars()).take_while(|(e, a)| e == a).count();
+                Some(format!("expected: {}\n     got: {a}\n          {}^", self.expected, " ".repeat(col)))
+            },
+            Err(e) => Some(format!("expected: {}\n   error: {e}", self.expected)),
+        }
+    }
+}
+
+/// Solves `day` for `input` and checks each part that has an expected
+/// answer. Parts without an expected answer aren't run.
+pub fn verify(expected: &Expected, day: u8, input: &str) -> Vec<Check> {
+    let parts: Vec<(u8, &str)> = expected.parts(day).collect();
+    let run_part = match parts.as_slice() {
+        [(p, _)] => Some(*p),
+        _ => None,
+    };
+    let answers = solver(day)
+        .ok_or_else(|| Error::no_solution(format!("there is no day {day}")))
+        .and_then(|solve| solve(input, run_part));
+    parts
+        .into_iter()
+        .map(|(part, exp)| Check {
+            day,
+            part,
+            expected: exp.to_string(),
+            actual: match &answers {
+                Ok(a) => a.iter()
+                    .find(|a| a.part == part)
+                    .map(|a| a.value.clone())
+                    .ok_or_else(|| Error::no_solution(format!("day {day} has no part {part}"))),
+                Err(e) => Err(e.clone()),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_test() {
+        let expected: Expected = r#"{
+            "21": { "1": 5, "2": "mxmxvkd,fvjkl,sqjhc" },
+            "25": { "1": "14897079", "2": 0 }
+        }"#.parse().unwrap();
+        assert_eq!(expected.days(), vec![21, 25]);
+        assert_eq!(expected.get(21, 1), Some("5"));
+
+        let checks = verify(&expected, 21, include_str!("day21.testinput"));
+        assert!(checks[0].passed());
+        assert!(!checks[1].passed());
+        assert_eq!(
+            checks[1].diff().unwrap(),
+            "expected: mxmxvkd,fvjkl,sqjhc\n     got: mxmxvkd,sqjhc,fvjkl\n                  ^",
+        );
+
+        let checks = verify(&expected, 25, "5764801\n17807724\n");
+        assert!(checks[0].passed());
+        assert_eq!(checks[1].actual, Err(Error::no_solution("day 25 has no part 2")));
+
+        let checks = verify(&expected, 21, "garbage\n");
+        assert!(checks.iter().all(|c| matches!(c.actual, Err(Error::Parse { line: 1, .. }))));
+
+        assert_eq!(
+            "{\n  \"1\": {\"3\": 1}}".parse::<Expected>(),
+            Err(Error::Parse { line: 2, column: 9, message: "day 1: invalid part: \"3\"".into() }),
+        );
+        assert!(matches!("{\"1\": ".parse::<Expected>(), Err(Error::Parse { line: 1, column: 6, .. })));
+    }
+}